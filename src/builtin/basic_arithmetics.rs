@@ -1,4 +1,4 @@
-use super::builtins::{div, sign};
+use super::builtins::{div, modulo, sign};
 
 /* Greater common (positive) divisor of two non-zero integers.
  * @param a non-zero integer
@@ -45,6 +45,32 @@ pub fn bezout(a: i64, b: i64) -> (i64, i64, i64) {
     }
 }
 
+/* Jacobi symbol (a/n), equal to the Legendre symbol when n is prime.
+   Computed by quadratic reciprocity without factoring n.
+   @param a integer
+   @param n odd positive integer.
+*/
+pub fn jacobi(a: i64, n: i64) -> i64 {
+    if n <= 0 || modulo(n, 2) == 0 {
+        panic!("n must be odd and > 0");
+    }
+    let mut a = modulo(a, n);
+    let mut n = n;
+    let mut result = 1;
+    while a != 0 {
+        let twos = a.trailing_zeros();
+        a >>= twos;
+        if twos % 2 == 1 && (modulo(n, 8) == 3 || modulo(n, 8) == 5) {
+            result = -result;
+        }
+        if modulo(a, 4) == 3 && modulo(n, 4) == 3 {
+            result = -result;
+        }
+        (a, n) = (modulo(n, a), a);
+    }
+    if n == 1 { result } else { 0 }
+}
+
 // ========================= TESTING =========================
 
 pub fn test_gcd() {
@@ -84,9 +110,38 @@ pub fn test_bezout() {
     }
 }
 
+pub fn test_jacobi() {
+    let cases = vec![
+        ((1, 1), 1),
+        ((2, 7), 1),
+        ((3, 7), -1),
+        ((5, 21), 1),
+        ((6, 21), 0),
+        ((-1, 13), 1),
+        ((-1, 11), -1),
+        ((1001, 9907), -1),
+        ((19, 45), 1),
+        ((8, 21), -1),
+    ];
+
+    for ele in cases {
+        let result = jacobi(ele.0.0, ele.0.1);
+        if result == ele.1 {
+            println!("jacobi({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "jacobi({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
 pub fn test_basic_arithmetics() {
     test_gcd();
     println!();
     test_bezout();
     println!();
+    test_jacobi();
+    println!();
 }
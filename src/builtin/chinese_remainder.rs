@@ -1 +1,142 @@
+use crate::integer::Integer;
+use crate::scalable::scalables::Scalable;
 
+/* Inverse of an integer modulo m, None when they are not coprime.
+   Written once for builtin and scalable integers.
+   @param a integer
+   @param m moduli, greater than 1.
+*/
+pub fn inverse<T: Integer>(a: &T, m: &T) -> Option<T> {
+    let (u, _, d) = a.modulo(m).bezout(m);
+    if d == T::one() {
+        Some(u.modulo(m))
+    } else {
+        None
+    }
+}
+
+/* Coefficients used by Garner's recombination.
+   Coefficient i is the inverse of the product of moduli before i modulo
+   the i-th moduli, first coefficient is 1 by convention.
+   @param moduli pairwise coprime moduli.
+*/
+pub fn crt_coefficients<T: Integer>(moduli: &[T]) -> Vec<T> {
+    let mut result = vec![T::one()];
+    let mut prod = moduli[0].clone();
+    for m in &moduli[1..] {
+        match inverse(&prod, m) {
+            Some(t) => result.push(t),
+            None => panic!("moduli must be pairwise coprime"),
+        }
+        prod = prod * m.clone();
+    }
+    result
+}
+
+/* Garner's recombination. Unique integer x in [0, m1 * ... * mk[ such
+   that x = ri mod mi for every i, given precomputed coefficients.
+   @param residues residues ri.
+   @param moduli pairwise coprime moduli mi.
+   @param coefficients output of `crt_coefficients` on moduli.
+*/
+pub fn garner<T: Integer>(residues: &[T], moduli: &[T], coefficients: &[T]) -> T {
+    let mut x = residues[0].modulo(&moduli[0]);
+    let mut prod = moduli[0].clone();
+    for i in 1..moduli.len() {
+        let diff = (residues[i].clone() - x.clone()).modulo(&moduli[i]);
+        let h = (diff * coefficients[i].clone()).modulo(&moduli[i]);
+        x = x + prod.clone() * h;
+        prod = prod * moduli[i].clone();
+    }
+    x
+}
+
+/* Chinese remainder theorem. Unique integer x in [0, m1 * ... * mk[
+   such that x = ri mod mi for every i.
+   @param residues residues ri.
+   @param moduli pairwise coprime moduli mi.
+*/
+pub fn chinese_remainder<T: Integer>(residues: &[T], moduli: &[T]) -> T {
+    if residues.len() != moduli.len() || moduli.is_empty() {
+        panic!("There must be as many residues as moduli, at least one");
+    }
+    garner(residues, moduli, &crt_coefficients(moduli))
+}
+
+// ========================= TESTING =========================
+
+pub fn test_inverse() {
+    let cases = vec![
+        ((3, 7), Some(5)),
+        ((10, 17), Some(12)),
+        ((6, 9), None),
+        ((-3, 7), Some(2)),
+    ];
+
+    for ele in cases {
+        let result = inverse(&ele.0.0, &ele.0.1);
+        if result == ele.1 {
+            println!("inverse({},{})={:?} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "inverse({},{})={:?} error: expected {:?}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_chinese_remainder() {
+    let cases = vec![
+        ((vec![2, 3, 2], vec![3, 5, 7]), 23),
+        ((vec![1, 2], vec![4, 9]), 29),
+        ((vec![0, 0, 0, 0], vec![2, 3, 5, 7]), 0),
+        ((vec![-1, -1], vec![11, 13]), 142),
+    ];
+
+    for ele in cases {
+        let result = chinese_remainder(&ele.0.0, &ele.0.1);
+        if result == ele.1 {
+            println!(
+                "chinese_remainder({:?},{:?})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "chinese_remainder({:?},{:?})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+
+    let moduli: Vec<Scalable> = [
+        "340282366920938463463374607431768211507",
+        "18446744073709551629",
+        "170141183460469231731687303715884105727",
+    ]
+    .iter()
+    .map(|x| Scalable::from_str_radix(x, 10).unwrap())
+    .collect();
+    let x = Scalable::from_str_radix(
+        "123456789012345678901234567890123456789012345678901234567890",
+        10,
+    )
+    .unwrap();
+    let residues: Vec<Scalable> = moduli.iter().map(|m| &x % m).collect();
+    let result = chinese_remainder(&residues, &moduli);
+    if result == x {
+        println!("chinese_remainder(scalable)={} passed", result);
+    } else {
+        println!(
+            "chinese_remainder(scalable)={} error: expected {}",
+            result, x
+        );
+    }
+}
+
+pub fn test_chinese_remainders() {
+    test_inverse();
+    println!();
+    test_chinese_remainder();
+    println!();
+}
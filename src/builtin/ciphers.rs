@@ -1,9 +1,8 @@
 /********** Cesar Cipher **********/
 
-use crate::builtin::{
-    basic_arithmetics::{bezout, gcd},
-    power::mod_power,
-};
+use crate::builtin::{basic_arithmetics::gcd, chinese_remainder::garner, power::mod_power};
+use crate::hash::sha256::sha256;
+use crate::keys::{ElGamalPrivateKey, ElGamalPublicKey, RsaKeyPair, RsaPrivateKey, RsaPublicKey};
 
 use super::builtins::modulo;
 
@@ -33,9 +32,18 @@ pub fn decrypt_cesar(k: i64, m: &[i64], b: i64) -> Vec<i64> {
     @param p prime number
     @param q prime number
 */
-pub fn generate_keys_rsa(p: i64, q: i64) -> ((i64, i64), (i64, i64)) {
-    let n = p * q;
-    let phi = (p - 1) * (q - 1);
+pub fn generate_keys_rsa(p: i64, q: i64) -> RsaKeyPair<i64> {
+    generate_keys_rsa_multi(&[p, q])
+}
+
+/* Generate multi-prime RSA ciphering keys.
+    Involved prime numbers need to be distinct, there must be at least
+    two of them. Private key keeps primes so that decryption goes through
+    chinese remainder theorem.
+    @param primes prime numbers whose product is the modulus.
+*/
+pub fn generate_keys_rsa_multi(primes: &[i64]) -> RsaKeyPair<i64> {
+    let phi: i64 = primes.iter().map(|r| r - 1).product();
     let mut e = -1;
     for n in (1..=phi - 1).rev() {
         if gcd(n, phi) == 1 {
@@ -46,23 +54,83 @@ pub fn generate_keys_rsa(p: i64, q: i64) -> ((i64, i64), (i64, i64)) {
     if e == -1 {
         panic!("No number");
     }
-    ((n, e), (n, bezout(e, phi).0))
+    RsaKeyPair::from_private(
+        RsaPrivateKey::from_primes(primes.to_vec(), e).expect("generated key is valid"),
+    )
 }
 
 /* Encryption using RSA cryptosystem.
    @param m integer hash of message
-   @param pub_key a tuple (n, e) composing public key of RSA cryptosystem.
+   @param pub_key public key (n, e) of RSA cryptosystem.
 */
-pub fn encrypt_rsa(m: i64, (n, e): (i64, i64)) -> i64 {
-    mod_power(m, e, n)
+pub fn encrypt_rsa(m: i64, pub_key: &RsaPublicKey<i64>) -> i64 {
+    mod_power(m, *pub_key.e(), *pub_key.n())
 }
 
 /* Decryption using RSA cryptosystem.
+   Works modulo each prime and recombines with chinese remainder theorem
+   when primes of the key are known.
    @param m integer hash of encrypted message.
-   @param pub_key a tuple (n, d) composing private key of RSA cryptosystem.
+   @param priv_key private key (n, d) of RSA cryptosystem.
+*/
+pub fn decrypt_rsa(m: i64, priv_key: &RsaPrivateKey<i64>) -> i64 {
+    let primes = priv_key.primes();
+    if primes.is_empty() {
+        return mod_power(m, *priv_key.d(), *priv_key.n());
+    }
+    let residues: Vec<i64> = primes
+        .iter()
+        .zip(priv_key.exponents())
+        .map(|(r, d)| mod_power(m, *d, *r))
+        .collect();
+    garner(&residues, primes, priv_key.coefficients())
+}
+
+/********** RSA Signature **********/
+
+/* Message representative of a byte message for toy keys: its SHA-256
+   digest read as a big endian integer reduced modulo n.
+*/
+fn message_representative(msg: &[u8], n: i64) -> i64 {
+    sha256(msg)
+        .iter()
+        .fold(0, |acc, b| modulo(acc * 256 + *b as i64, n))
+}
+
+/* RSA signature of a message representative, m^d mod n.
+   @param m message representative, in [0, n[.
+   @param priv_key private key of RSA cryptosystem.
 */
-pub fn decrypt_rsa(m: i64, (n, d): (i64, i64)) -> i64 {
-    mod_power(m, d, n)
+pub fn sign_rsa(m: i64, priv_key: &RsaPrivateKey<i64>) -> i64 {
+    decrypt_rsa(m, priv_key)
+}
+
+/* Check an RSA signature, s^e mod n must give back the message
+   representative.
+   @param m message representative, in [0, n[.
+   @param s signature
+   @param pub_key public key of RSA cryptosystem.
+*/
+pub fn verify_rsa(m: i64, s: i64, pub_key: &RsaPublicKey<i64>) -> bool {
+    0 <= s && s < *pub_key.n() && encrypt_rsa(s, pub_key) == m
+}
+
+/* Hash-then-sign a byte message with RSA.
+   @param msg message to sign.
+   @param priv_key private key of RSA cryptosystem.
+*/
+pub fn sign_rsa_message(msg: &[u8], priv_key: &RsaPrivateKey<i64>) -> i64 {
+    sign_rsa(message_representative(msg, *priv_key.n()), priv_key)
+}
+
+/* Check an RSA signature of a byte message produced by
+   `sign_rsa_message`.
+   @param msg signed message.
+   @param s signature
+   @param pub_key public key of RSA cryptosystem.
+*/
+pub fn verify_rsa_message(msg: &[u8], s: i64, pub_key: &RsaPublicKey<i64>) -> bool {
+    verify_rsa(message_representative(msg, *pub_key.n()), s, pub_key)
 }
 
 /********** ElGamal Cipher **********/
@@ -71,32 +139,31 @@ pub fn decrypt_rsa(m: i64, (n, d): (i64, i64)) -> i64 {
    where p is prime and g primitive root in F_p.
    @param p is prime having form 2*q + 1 for prime q.
 */
-pub fn public_data_g(p: i64) -> (i64, i64) {
+pub fn public_data_g(_p: i64) -> (i64, i64) {
     (0, 0)
 }
 
-/* Generate ElGamal public and private keys.
-   @param pub_data a tuple (g, p) of public data for ElGamal cryptosystem.
+/* Generate ElGamal public and private keys. Not implemented yet.
+   @param g generator of the multiplicative group of F_p.
+   @param p prime modulus.
 */
-pub fn generate_keys_g(g: i64, p: i64) -> (i64, i64) {
-    (0, 0)
+pub fn generate_keys_g(_g: i64, _p: i64) -> (ElGamalPublicKey<i64>, ElGamalPrivateKey<i64>) {
+    unimplemented!("ElGamal key generation")
 }
 
 /* ElGamal encryption process.
    @param msg message to be encrypted.
-   @param pub_data a tuple (g, p) of ElGamal public data.
-   @param kA ElGamal public key.
+   @param pub_key ElGamal public key.
 */
-pub fn encrypt_g(msg: i64, (g, p): (i64, i64), kA: i64) -> (i64, i64) {
+pub fn encrypt_g(_msg: i64, _pub_key: &ElGamalPublicKey<i64>) -> (i64, i64) {
     (0, 0)
 }
 
 /* ElGamal decryption process.
    @param msg a tuple (msg_a, msg_b) forming an encrypted ElGamal message.
-   @param a private key
-   @param pub_data a tuple (g, p) of public data for ElGamal cryptosystem.
+   @param priv_key ElGamal private key.
 */
-pub fn decrypt_g((msg_a, msg_b): (i64, i64), a: i64, (g, p): (i64, i64)) -> i64 {
+pub fn decrypt_g(_msg: (i64, i64), _priv_key: &ElGamalPrivateKey<i64>) -> i64 {
     0
 }
 
@@ -160,7 +227,8 @@ pub fn test_generate_keys_rsa() {
 
     for ele in cases {
         let ((p, q), exp) = ele;
-        let ((_, e), (_, d)) = generate_keys_rsa(p, q);
+        let keys = generate_keys_rsa(p, q);
+        let (e, d) = (*keys.public.e(), *keys.private.d());
         let phi = (p - 1) * (q - 1);
         if modulo(e, phi) == 1 {
             if !exp {
@@ -180,11 +248,11 @@ pub fn test_generate_keys_rsa() {
 }
 
 pub fn test_encrypt_rsa() {
-    let cases: Vec<((i64, (i64, i64)), i64)> = vec![((281237, (99400891, 36199003)), 70133953)];
+    let cases = vec![((281237, (99400891, 36199003)), 70133953)];
 
     for ele in cases {
         let ((m, (n, e)), exp) = ele;
-        let result = encrypt_rsa(m, (n, e));
+        let result = encrypt_rsa(m, &RsaPublicKey::new(n, e).unwrap());
         if result == exp {
             println!("encrypt_rsa({m},({n},{e}))={result} passed");
         } else {
@@ -194,11 +262,11 @@ pub fn test_encrypt_rsa() {
 }
 
 pub fn test_decrypt_rsa() {
-    let cases: Vec<((i64, (i64, i64)), i64)> = vec![((70133953, (99400891, 30869683)), 281237)];
+    let cases = vec![((70133953, (99400891, 36199003, 30869683)), 281237)];
 
     for ele in cases {
-        let ((m, (n, d)), exp) = ele;
-        let result = decrypt_rsa(m, (n, d));
+        let ((m, (n, e, d)), exp) = ele;
+        let result = decrypt_rsa(m, &RsaPrivateKey::new(n, e, d).unwrap());
         if result == exp {
             println!("encrypt_rsa({m},({n},{d}))={result} passed");
         } else {
@@ -207,6 +275,51 @@ pub fn test_decrypt_rsa() {
     }
 }
 
+pub fn test_multi_prime_rsa() {
+    let cases = vec![
+        (vec![9967, 9973], 281237),
+        (vec![463, 467, 479], 1234567),
+        (vec![61, 67, 71, 73], 2024),
+    ];
+
+    for ele in cases {
+        let (primes, m) = ele;
+        let keys = generate_keys_rsa_multi(&primes);
+        let c = encrypt_rsa(m, &keys.public);
+        let result = decrypt_rsa(c, &keys.private);
+        let plain = mod_power(c, *keys.private.d(), *keys.private.n());
+        if result == m && result == plain {
+            println!("decrypt_rsa(encrypt_rsa({m}),{primes:?})={result} passed");
+        } else {
+            println!(
+                "decrypt_rsa(encrypt_rsa({m}),{primes:?})={result} error: expected {m}, plain path gave {plain}"
+            );
+        }
+    }
+}
+
+pub fn test_sign_rsa() {
+    let cases = vec![(9967, 9973), (463, 467)];
+
+    for ele in cases {
+        let (p, q) = ele;
+        let keys = RsaKeyPair::from_private(RsaPrivateKey::from_primes(vec![p, q], 65537).unwrap());
+        let s = sign_rsa_message(b"attack at dawn", &keys.private);
+        let result = (
+            verify_rsa_message(b"attack at dawn", s, &keys.public),
+            verify_rsa_message(b"attack at dusk", s, &keys.public),
+            verify_rsa_message(b"attack at dawn", modulo(s + 1, p * q), &keys.public),
+        );
+        if result == (true, false, false) {
+            println!("sign_rsa_message(({p},{q}))={s} passed");
+        } else {
+            println!(
+                "sign_rsa_message(({p},{q}))={s} error: expected (true, false, false) on (genuine, tampered message, tampered signature), got {result:?}"
+            );
+        }
+    }
+}
+
 pub fn test_ciphers() {
     test_encrypt_cesar();
     println!();
@@ -217,4 +330,8 @@ pub fn test_ciphers() {
     test_encrypt_rsa();
     println!();
     test_decrypt_rsa();
+    println!();
+    test_multi_prime_rsa();
+    println!();
+    test_sign_rsa();
 }
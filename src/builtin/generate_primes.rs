@@ -1,6 +1,8 @@
-use super::{builtins::modulo, test_primes::is_prime};
+use super::test_primes::{is_prime, miller_rabin};
 use std::fs;
 use std::io::{Read, Write};
+use std::ops::RangeInclusive;
+use std::thread;
 
 /* List composed of 2 and then odd integers starting at 3.
    @param n limit of list of odd integers, minimum value is 2.
@@ -16,6 +18,76 @@ pub fn init_eratosthenes(n: i64) -> Vec<i64> {
     result
 }
 
+/* Bitset over odd integers up to a limit, bit i standing for 2i + 1
+   and being set when 2i + 1 is composite.
+*/
+fn odd_composites(n: i64) -> Vec<u64> {
+    let size = (n as usize - 1) / 2 + 1;
+    let mut composite = vec![0u64; size.div_ceil(64)];
+    composite[0] |= 1;
+    let mut i = 1;
+    while (2 * i + 1) * (2 * i + 1) <= n as usize {
+        if composite[i / 64] >> (i % 64) & 1 == 0 {
+            let p = 2 * i + 1;
+            for j in (p * p / 2..size).step_by(p) {
+                composite[j / 64] |= 1 << (j % 64);
+            }
+        }
+        i += 1;
+    }
+    composite
+}
+
+/* Iterator over the primes up to a limit, in increasing order, reading
+   them off an odd only sieve without building the list.
+*/
+pub struct Primes {
+    limit: i64,
+    composite: Vec<u64>,
+    next: usize,
+}
+
+impl Iterator for Primes {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        if self.next == 0 {
+            self.next = 1;
+            if self.limit >= 2 {
+                return Some(2);
+            }
+        }
+        let size = (self.limit.max(1) as usize - 1) / 2 + 1;
+        while self.next < size {
+            let word = !self.composite[self.next / 64] >> (self.next % 64);
+            if word == 0 {
+                self.next = (self.next / 64 + 1) * 64;
+                continue;
+            }
+            let i = self.next + word.trailing_zeros() as usize;
+            if i >= size {
+                break;
+            }
+            self.next = i + 1;
+            return Some(2 * i as i64 + 1);
+        }
+        self.next = size;
+        None
+    }
+}
+
+/* Primes up to n as an iterator, sieving odd integers only and crossing
+   off multiples of primes up to sqrt(n).
+   @param n limit of primes, starting at 2.
+*/
+pub fn primes(n: i64) -> Primes {
+    Primes {
+        limit: n,
+        composite: odd_composites(n.max(1)),
+        next: 0,
+    }
+}
+
 /* Eratosthene sieve.
     @param n limit of list of primes, starting at 2.
 */
@@ -23,23 +95,225 @@ pub fn eratosthenes(n: i64) -> Vec<i64> {
     if n < 2 {
         panic!("n must be >= 2");
     }
-    let init = init_eratosthenes(n);
-    let mut result = vec![];
-    for elt in init {
-        let mut is_multiple = false;
-        for r in result.iter() {
-            if modulo(elt, *r) == 0 {
-                is_multiple = true;
+    primes(n).collect()
+}
+
+/* Sieve of Atkin, primes being the integers with an odd number of
+   representations by the quadratic forms 4x^2 + y^2, 3x^2 + y^2 and
+   3x^2 - y^2 (x > y) matching their residue mod 12, once multiples of
+   squares of primes are removed.
+   @param n limit of list of primes, starting at 2.
+*/
+pub fn atkin(n: i64) -> Vec<i64> {
+    if n < 2 {
+        panic!("n must be >= 2");
+    }
+    let mut sieve = vec![false; n as usize + 1];
+    let mut x = 1;
+    while x * x <= n {
+        let mut y = 1;
+        while y * y <= n {
+            let k = 4 * x * x + y * y;
+            if k <= n && (k % 12 == 1 || k % 12 == 5) {
+                sieve[k as usize] ^= true;
+            }
+            let k = 3 * x * x + y * y;
+            if k <= n && k % 12 == 7 {
+                sieve[k as usize] ^= true;
+            }
+            let k = 3 * x * x - y * y;
+            if x > y && k <= n && k % 12 == 11 {
+                sieve[k as usize] ^= true;
+            }
+            y += 1;
+        }
+        x += 1;
+    }
+    let mut r = 5;
+    while r * r <= n {
+        if sieve[r as usize] {
+            for k in (r * r..=n).step_by((r * r) as usize) {
+                sieve[k as usize] = false;
+            }
+        }
+        r += 1;
+    }
+    let mut result: Vec<i64> = [2, 3].into_iter().filter(|p| *p <= n).collect();
+    result.extend((5..=n).filter(|k| sieve[*k as usize]));
+    result
+}
+
+/* Modulus of the wheel used by `wheel_sieve`, product of 2, 3, 5 and 7. */
+const WHEEL: i64 = 210;
+
+/* Wheel factorization sieve. Only the 48 residues mod 2*3*5*7 prime to
+   210 are stored, and each prime p up to sqrt(n) crosses off the
+   products p*q for q on the wheel from p onwards.
+   @param n limit of list of primes, starting at 2.
+*/
+pub fn wheel_sieve(n: i64) -> Vec<i64> {
+    if n < 2 {
+        panic!("n must be >= 2");
+    }
+    let spokes: Vec<i64> = (1..WHEEL)
+        .filter(|r| [2, 3, 5, 7].iter().all(|p| r % p != 0))
+        .collect();
+    let mut position = [0; WHEEL as usize];
+    for (i, r) in spokes.iter().enumerate() {
+        position[*r as usize] = i;
+    }
+    let turn = spokes.len();
+    let value = |i: usize| (i / turn) as i64 * WHEEL + spokes[i % turn];
+    let index = |k: i64| (k / WHEEL) as usize * turn + position[(k % WHEEL) as usize];
+
+    let size = (0..).take_while(|i| value(*i) <= n).count();
+    let mut composite = vec![false; size];
+    for i in 1..size {
+        let p = value(i);
+        if p * p > n {
+            break;
+        }
+        if composite[i] {
+            continue;
+        }
+        for j in i..size {
+            let m = p * value(j);
+            if m > n {
                 break;
             }
+            composite[index(m)] = true;
         }
-        if !is_multiple {
-            result.push(elt);
+    }
+    let mut result: Vec<i64> = [2, 3, 5, 7].into_iter().filter(|p| *p <= n).collect();
+    result.extend((1..size).filter(|i| !composite[*i]).map(value));
+    result
+}
+
+/* Odd integers sieved at once by `segmented_sieve`, one byte each so
+   that a segment fits in a 32 KiB L1 cache.
+*/
+const SEGMENT_SIZE: usize = 1 << 15;
+
+/* Segmented sieve listing the primes in [lo, hi]. Odd integers are
+   sieved by segments of SEGMENT_SIZE with the base primes up to
+   sqrt(hi), so memory stays small however large lo is.
+   @param lo lower bound of the range.
+   @param hi upper bound of the range.
+*/
+pub fn segmented_sieve(lo: i64, hi: i64) -> Vec<i64> {
+    if hi < 2 || hi < lo {
+        return vec![];
+    }
+    let base: Vec<i64> = primes(hi.isqrt()).skip(1).collect();
+    sieve_segments(lo, hi, &base)
+}
+
+/* Primes in [lo, hi] sieved segment by segment.
+   @param lo lower bound of the range.
+   @param hi upper bound of the range.
+   @param base odd primes up to at least sqrt(hi).
+*/
+fn sieve_segments(lo: i64, hi: i64, base: &[i64]) -> Vec<i64> {
+    let mut result = vec![];
+    if hi < 2 || hi < lo {
+        return result;
+    }
+    if lo <= 2 {
+        result.push(2);
+    }
+    let mut composite = vec![false; SEGMENT_SIZE];
+    let mut start = lo.max(3) | 1;
+    while start <= hi {
+        let end = start.saturating_add(2 * (SEGMENT_SIZE as i64 - 1)).min(hi);
+        let len = ((end - start) / 2 + 1) as usize;
+        composite[..len].fill(false);
+        for &p in base.iter().take_while(|p| **p * **p <= end) {
+            let mut m = (p * p).max((start + p - 1) / p * p);
+            if m % 2 == 0 {
+                m += p;
+            }
+            for k in (((m - start) / 2) as usize..len).step_by(p as usize) {
+                composite[k] = true;
+            }
+        }
+        result.extend(
+            (0..len)
+                .filter(|k| !composite[*k])
+                .map(|k| start + 2 * k as i64),
+        );
+        if end >= hi - 1 {
+            break;
         }
+        start = end + 2;
     }
     result
 }
 
+/* Split [lo, hi] into at most parts consecutive non empty ranges of
+   about the same length.
+*/
+fn split_range(lo: i64, hi: i64, parts: usize) -> Vec<(i64, i64)> {
+    if hi < lo {
+        return vec![];
+    }
+    let len = (hi - lo) as u64 + 1;
+    let step = len.div_ceil(parts as u64) as i64;
+    let mut result = vec![];
+    let mut start = lo;
+    loop {
+        let end = start.saturating_add(step - 1).min(hi);
+        result.push((start, end));
+        if end == hi {
+            return result;
+        }
+        start = end + 1;
+    }
+}
+
+/* Run f on each range of a split of [lo, hi] in its own thread and
+   concatenate the results in the order of the ranges.
+   @param lo lower bound of the range.
+   @param hi upper bound of the range.
+   @param workers number of threads, at least 1.
+   @param f function applied to each sub range.
+*/
+fn parallel_ranges<T, F>(lo: i64, hi: i64, workers: usize, f: F) -> Vec<T>
+where
+    T: Send,
+    F: Fn(i64, i64) -> Vec<T> + Sync,
+{
+    if workers == 0 {
+        panic!("workers must be >= 1");
+    }
+    let f = &f;
+    thread::scope(|scope| {
+        let handles: Vec<_> = split_range(lo, hi, workers)
+            .into_iter()
+            .map(|(start, end)| scope.spawn(move || f(start, end)))
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+/* Primes in [lo, hi] as `segmented_sieve` gives them, the range being
+   split between workers threads sharing the base primes.
+   @param lo lower bound of the range.
+   @param hi upper bound of the range.
+   @param workers number of threads, at least 1.
+*/
+pub fn parallel_segmented_sieve(lo: i64, hi: i64, workers: usize) -> Vec<i64> {
+    if hi < 2 || hi < lo {
+        return vec![];
+    }
+    let base: Vec<i64> = primes(hi.isqrt()).skip(1).collect();
+    parallel_ranges(lo, hi, workers, |start, end| {
+        sieve_segments(start, end, &base)
+    })
+}
+
 /* Write a list of prime numbers up to limit into a txt file.
     @param n limit of prime numbers up to which to build up a list of primes.
     @param file path to write to.
@@ -88,11 +362,11 @@ pub fn last_two(l: &[i64]) -> (i64, i64) {
 
 /* Finding couples of primes where second entry is twice the first
    plus 1.
-   @param limit positive integer bounding searched for primes.
+   @param range integers in which first entries are searched for.
    @param isprime function testing for (pseudo)primality.
 */
-pub fn double_primes(limit: i64, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
-    let init = eratosthenes(limit);
+pub fn double_primes(range: RangeInclusive<i64>, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
+    let init = segmented_sieve(*range.start(), *range.end());
     let mut result = vec![];
     for elt in init {
         if isprime(2 * elt + 1) {
@@ -102,12 +376,27 @@ pub fn double_primes(limit: i64, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
     result
 }
 
+/* `double_primes` with the range split between workers threads.
+   @param range integers in which first entries are searched for.
+   @param isprime function testing for (pseudo)primality.
+   @param workers number of threads, at least 1.
+*/
+pub fn parallel_double_primes(
+    range: RangeInclusive<i64>,
+    isprime: fn(i64) -> bool,
+    workers: usize,
+) -> Vec<(i64, i64)> {
+    parallel_ranges(*range.start(), *range.end(), workers, |start, end| {
+        double_primes(start..=end, isprime)
+    })
+}
+
 /* Finding twin primes.
-   @param limit positive integer bounding searched for primes.
+   @param range integers in which the smaller twins are searched for.
    @param isprime function testing for (pseudo)primality.
 */
-pub fn twin_primes(limit: i64, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
-    let init = eratosthenes(limit);
+pub fn twin_primes(range: RangeInclusive<i64>, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
+    let init = segmented_sieve(*range.start(), *range.end());
     let mut result = vec![];
     for elt in init {
         if isprime(elt + 2) {
@@ -117,6 +406,21 @@ pub fn twin_primes(limit: i64, isprime: fn(i64) -> bool) -> Vec<(i64, i64)> {
     result
 }
 
+/* `twin_primes` with the range split between workers threads.
+   @param range integers in which the smaller twins are searched for.
+   @param isprime function testing for (pseudo)primality.
+   @param workers number of threads, at least 1.
+*/
+pub fn parallel_twin_primes(
+    range: RangeInclusive<i64>,
+    isprime: fn(i64) -> bool,
+    workers: usize,
+) -> Vec<(i64, i64)> {
+    parallel_ranges(*range.start(), *range.end(), workers, |start, end| {
+        twin_primes(start..=end, isprime)
+    })
+}
+
 // ========================= TESTING =========================
 
 pub fn test_init_eratosthenes() {
@@ -141,6 +445,10 @@ pub fn test_eratosthenes() {
         (3, vec![2, 3]),
         (6, vec![2, 3, 5]),
         (25, vec![2, 3, 5, 7, 11, 13, 17, 19, 23]),
+        (
+            49,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47],
+        ),
     ];
 
     for ele in cases {
@@ -156,16 +464,200 @@ pub fn test_eratosthenes() {
     }
 }
 
+pub fn test_primes() {
+    let cases = vec![
+        (1, vec![]),
+        (2, vec![2]),
+        (9, vec![2, 3, 5, 7]),
+        (
+            131,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97, 101, 103, 107, 109, 113, 127, 131,
+            ],
+        ),
+    ];
+
+    for ele in cases {
+        let result: Vec<i64> = primes(ele.0).collect();
+        if result == ele.1 {
+            println!("primes({})={:?} passed", ele.0, result);
+        } else {
+            println!("primes({})={:?} error: expected {:?}", ele.0, result, ele.1);
+        }
+    }
+
+    let cases = vec![(1000, 168), (1_000_000, 78498)];
+
+    for ele in cases {
+        let result = primes(ele.0).count();
+        let agree = primes(ele.0).all(is_prime);
+        if result == ele.1 && agree {
+            println!("primes({}).count()={} passed", ele.0, result);
+        } else {
+            println!(
+                "primes({}).count()={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_segmented_sieve() {
+    let cases = vec![
+        ((0, 1), vec![]),
+        ((2, 2), vec![2]),
+        ((20, 10), vec![]),
+        ((-5, 12), vec![2, 3, 5, 7, 11]),
+        ((90, 130), vec![97, 101, 103, 107, 109, 113, 127]),
+    ];
+
+    for ele in cases {
+        let result = segmented_sieve(ele.0.0, ele.0.1);
+        if result == ele.1 {
+            println!(
+                "segmented_sieve({},{})={:?} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "segmented_sieve({},{})={:?} error: expected {:?}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+
+    let all = eratosthenes(300_000);
+    let cases = vec![
+        (2, 300_000),
+        (65_535, 65_539),
+        (1000, 131_073),
+        (99_991, 300_000),
+    ];
+
+    for ele in cases {
+        let result = segmented_sieve(ele.0, ele.1);
+        let expected: Vec<i64> = all
+            .iter()
+            .copied()
+            .filter(|p| ele.0 <= *p && *p <= ele.1)
+            .collect();
+        if result == expected {
+            println!("segmented_sieve({},{}) passed", ele.0, ele.1);
+        } else {
+            println!(
+                "segmented_sieve({},{}) error: expected {:?}",
+                ele.0, ele.1, expected
+            );
+        }
+    }
+
+    let (lo, hi) = (1_000_000_000_000, 1_000_001_000_000);
+    let result = segmented_sieve(lo, hi);
+    let ends = (result.first(), result.last());
+    let expected = (Some(&1_000_000_000_039), Some(&1_000_000_999_999));
+    if result.len() == 36249 && ends == expected {
+        println!("segmented_sieve({lo},{hi}).len()={} passed", result.len());
+    } else {
+        println!(
+            "segmented_sieve({lo},{hi}).len()={} error: expected 36249 from {:?}",
+            result.len(),
+            expected
+        );
+    }
+}
+
+pub fn test_atkin() {
+    let cases = vec![
+        (2, vec![2]),
+        (3, vec![2, 3]),
+        (6, vec![2, 3, 5]),
+        (
+            49,
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47],
+        ),
+    ];
+
+    for ele in cases {
+        let result = atkin(ele.0);
+        if result == ele.1 {
+            println!("atkin({})={:?} passed", ele.0, result);
+        } else {
+            println!("atkin({})={:?} error: expected {:?}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_wheel_sieve() {
+    let cases = vec![
+        (2, vec![2]),
+        (6, vec![2, 3, 5]),
+        (11, vec![2, 3, 5, 7, 11]),
+        (
+            223,
+            vec![
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79,
+                83, 89, 97, 101, 103, 107, 109, 113, 127, 131, 137, 139, 149, 151, 157, 163, 167,
+                173, 179, 181, 191, 193, 197, 199, 211, 223,
+            ],
+        ),
+    ];
+
+    for ele in cases {
+        let result = wheel_sieve(ele.0);
+        if result == ele.1 {
+            println!("wheel_sieve({})={:?} passed", ele.0, result);
+        } else {
+            println!(
+                "wheel_sieve({})={:?} error: expected {:?}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_sieves_agree() {
+    let n = 10_000_000;
+    let expected = eratosthenes(n);
+    let sieves: Vec<(&str, Vec<i64>)> = vec![
+        ("atkin", atkin(n)),
+        ("wheel_sieve", wheel_sieve(n)),
+        ("segmented_sieve", segmented_sieve(2, n)),
+    ];
+
+    for (name, result) in sieves {
+        if result == expected {
+            println!("{name}({n}) agrees with eratosthenes({n}) passed");
+        } else {
+            println!(
+                "{name}({n}) error: {} primes, expected {}",
+                result.len(),
+                expected.len()
+            );
+        }
+    }
+}
+
 pub fn test_double_primes() {
-    let cases = vec![((20, is_prime), vec![(2, 5), (3, 7), (5, 11), (11, 23)])];
+    let cases = vec![
+        (
+            (2..=20, is_prime as fn(i64) -> bool),
+            vec![(2, 5), (3, 7), (5, 11), (11, 23)],
+        ),
+        ((10..=30, is_prime), vec![(11, 23), (23, 47), (29, 59)]),
+        (
+            (1_000_000_000_000..=1_000_000_000_300, miller_rabin),
+            vec![(1_000_000_000_061, 2_000_000_000_123)],
+        ),
+    ];
 
     for ele in cases {
-        let result = double_primes(ele.0.0, ele.0.1);
+        let result = double_primes(ele.0.0.clone(), ele.0.1);
         if result == ele.1 {
-            println!("double_primes({},is_prime)={:?} passed", ele.0.0, result);
+            println!("double_primes({:?})={:?} passed", ele.0.0, result);
         } else {
             println!(
-                "double_primes({},is_prime)={:?} error: expected {:?}",
+                "double_primes({:?})={:?} error: expected {:?}",
                 ele.0.0, result, ele.1
             );
         }
@@ -173,26 +665,101 @@ pub fn test_double_primes() {
 }
 
 pub fn test_twin_primes() {
-    let cases = vec![((20, is_prime), vec![(3, 5), (5, 7), (11, 13), (17, 19)])];
+    let cases = vec![
+        (
+            (2..=20, is_prime as fn(i64) -> bool),
+            vec![(3, 5), (5, 7), (11, 13), (17, 19)],
+        ),
+        ((12..=45, is_prime), vec![(17, 19), (29, 31), (41, 43)]),
+        (
+            (1_000_000_000_000..=1_000_000_001_000, miller_rabin),
+            vec![
+                (1_000_000_000_061, 1_000_000_000_063),
+                (1_000_000_000_331, 1_000_000_000_333),
+                (1_000_000_000_787, 1_000_000_000_789),
+                (1_000_000_000_931, 1_000_000_000_933),
+            ],
+        ),
+    ];
 
     for ele in cases {
-        let result = twin_primes(ele.0.0, ele.0.1);
+        let result = twin_primes(ele.0.0.clone(), ele.0.1);
         if result == ele.1 {
-            println!("twin_primes({},is_prime)={:?} passed", ele.0.0, result);
+            println!("twin_primes({:?})={:?} passed", ele.0.0, result);
         } else {
             println!(
-                "twin_primes({},is_prime)={:?} error: expected {:?}",
+                "twin_primes({:?})={:?} error: expected {:?}",
                 ele.0.0, result, ele.1
             );
         }
     }
 }
 
+pub fn test_parallel_segmented_sieve() {
+    let cases = vec![
+        (2, 1_000_000, 1),
+        (2, 1_000_000, 4),
+        (0, 10, 16),
+        (999_000, 1_001_000, 3),
+        (1_000_000_000_000, 1_000_000_200_000, 8),
+    ];
+
+    for (lo, hi, workers) in cases {
+        let result = parallel_segmented_sieve(lo, hi, workers);
+        let expected = segmented_sieve(lo, hi);
+        if result == expected {
+            println!("parallel_segmented_sieve({lo},{hi},{workers}) passed");
+        } else {
+            println!(
+                "parallel_segmented_sieve({lo},{hi},{workers}) error: {} primes, expected {}",
+                result.len(),
+                expected.len()
+            );
+        }
+    }
+}
+
+pub fn test_parallel_prime_scans() {
+    let cases = vec![(2..=100_000, 1), (2..=100_000, 5), (1_000..=1_030, 7)];
+
+    for (range, workers) in cases {
+        let twins = parallel_twin_primes(range.clone(), miller_rabin, workers);
+        let doubles = parallel_double_primes(range.clone(), miller_rabin, workers);
+        let expected = (
+            twin_primes(range.clone(), miller_rabin),
+            double_primes(range.clone(), miller_rabin),
+        );
+        if (twins.clone(), doubles.clone()) == expected {
+            println!("parallel_prime_scans({range:?},{workers}) passed");
+        } else {
+            println!(
+                "parallel_prime_scans({range:?},{workers})={:?} error: expected {:?}",
+                (twins, doubles),
+                expected
+            );
+        }
+    }
+}
+
 pub fn test_generate_primes() {
     test_init_eratosthenes();
     println!();
     test_eratosthenes();
     println!();
+    test_primes();
+    println!();
+    test_segmented_sieve();
+    println!();
+    test_atkin();
+    println!();
+    test_wheel_sieve();
+    println!();
+    test_sieves_agree();
+    println!();
+    test_parallel_segmented_sieve();
+    println!();
+    test_parallel_prime_scans();
+    println!();
     test_double_primes();
     println!();
     test_twin_primes();
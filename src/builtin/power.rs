@@ -57,6 +57,36 @@ pub fn prime_mod_power(x: i64, n: i64, p: i64) -> i64 {
     mod_power(x, n, p) // this is not the way to do but for now I will leave it like this
 }
 
+/* Modular product through a 128 bits intermediate, exact for every
+   modular base up to i64::MAX where `x * y` would overflow.
+   @param x first factor
+   @param y second factor
+   @param m modular base
+*/
+pub fn mul_mod(x: i64, y: i64, m: i64) -> i64 {
+    (x as i128 * y as i128).rem_euclid(m as i128) as i64
+}
+
+/* Fast modular exponentiation for any modular base up to i64::MAX,
+   products going through `mul_mod`. Logarithmic complexity.
+   @param x base
+   @param n exponent, non negative
+   @param m modular base
+*/
+pub fn wide_mod_power(x: i64, n: i64, m: i64) -> i64 {
+    let mut result = modulo(1, m);
+    let mut base = modulo(x, m);
+    let mut n = n;
+    while n > 0 {
+        if n & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        n >>= 1;
+    }
+    result
+}
+
 // ========================= TESTING =========================
 
 pub fn test_pow() {
@@ -169,6 +199,32 @@ pub fn test_prime_mod_power() {
     }
 }
 
+pub fn test_wide_mod_power() {
+    let cases = vec![
+        ((2, 5, 17), 15),
+        ((-2, 3, 9), 1),
+        ((5, 0, 1), 0),
+        ((2, 62, i64::MAX), 4611686018427387904),
+        ((3, 1000000, 9223372036854775783), 4745223489584898946),
+        ((-7, 2305843009213693950, 2305843009213693951), 1),
+    ];
+
+    for ele in cases {
+        let result = wide_mod_power(ele.0.0, ele.0.1, ele.0.2);
+        if result == ele.1 {
+            println!(
+                "wide_mod_power({},{},{})={} passed",
+                ele.0.0, ele.0.1, ele.0.2, result
+            );
+        } else {
+            println!(
+                "wide_mod_power({},{},{})={} error: expected {}",
+                ele.0.0, ele.0.1, ele.0.2, result, ele.1
+            );
+        }
+    }
+}
+
 pub fn test_powers() {
     test_pow();
     println!();
@@ -178,4 +234,6 @@ pub fn test_powers() {
     println!();
     test_prime_mod_power();
     println!();
+    test_wide_mod_power();
+    println!();
 }
@@ -1,4 +1,10 @@
-use super::{builtins::modulo, power::mod_power};
+use super::{
+    basic_arithmetics::{bezout, jacobi},
+    builtins::modulo,
+    generate_primes::eratosthenes,
+    power::{mod_power, mul_mod, wide_mod_power},
+};
+use crate::rng::{Rng, SplitMix64, uniform_i64};
 
 /* Deterministic primality test */
 pub fn is_prime(n: i64) -> bool {
@@ -25,6 +31,183 @@ pub fn is_pseudo_prime(p: i64, test_seq: &[i64]) -> bool {
     true
 }
 
+/* Primality test based on Euler's criterion: for prime p and a coprime
+   to p, a^((p - 1) / 2) = (a/p) mod p. Unlike Fermat's test no composite
+   passes it for every base, Carmichael numbers included. Bases multiple
+   of p tell nothing and are skipped, as `is_pseudo_prime` lets them
+   pass, while any other base sharing a factor with p proves it composite.
+   @param p odd integer greater than 2.
+   @param test_seq sequence of integers against which to test
+*/
+pub fn is_euler_pseudo_prime(p: i64, test_seq: &[i64]) -> bool {
+    for elt in test_seq {
+        if modulo(*elt, p) == 0 {
+            continue;
+        }
+        let j = jacobi(*elt, p);
+        if j == 0 || wide_mod_power(*elt, (p - 1) / 2, p) != modulo(j, p) {
+            return false;
+        }
+    }
+    true
+}
+
+/* Solovay-Strassen primality test with random bases in [2, n - 1]. A
+   composite passes a round with probability at most 1/2, so at most
+   2^-rounds overall.
+   @param n tested integer
+   @param rounds number of random bases.
+   @param rng random generator drawing the bases.
+*/
+pub fn solovay_strassen(n: i64, rounds: usize, rng: &mut impl Rng) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if n == 2 {
+        return true;
+    }
+    if modulo(n, 2) == 0 {
+        return false;
+    }
+    (0..rounds).all(|_| is_euler_pseudo_prime(n, &[2 + uniform_i64(n - 2, rng)]))
+}
+
+/* Witnesses making Miller-Rabin deterministic on every 64 bits integer. */
+const MILLER_RABIN_WITNESSES: [i64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+/* Strong probable prime test to a single base: writing n - 1 = d * 2^s
+   with d odd, n passes when a^d = 1 or a^(d * 2^r) = -1 mod n for some
+   r < s.
+   @param n odd integer greater than 2.
+   @param a base.
+*/
+pub fn is_strong_pseudo_prime(n: i64, a: i64) -> bool {
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    let mut x = wide_mod_power(a, d, n);
+    if x == 1 || x == n - 1 || modulo(a, n) == 0 {
+        return true;
+    }
+    for _ in 1..s {
+        x = mul_mod(x, x, n);
+        if x == n - 1 {
+            return true;
+        }
+    }
+    false
+}
+
+/* Miller-Rabin primality test. Deterministic for every i64 since no
+   composite below 3.18 * 10^23 is a strong pseudo prime to all of the
+   first twelve primes.
+   @param n tested integer
+*/
+pub fn miller_rabin(n: i64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for a in MILLER_RABIN_WITNESSES {
+        if n == a {
+            return true;
+        }
+        if modulo(n, a) == 0 {
+            return false;
+        }
+    }
+    MILLER_RABIN_WITNESSES
+        .iter()
+        .all(|&a| is_strong_pseudo_prime(n, a))
+}
+
+/* Lucas sequences U_k(P, Q) and V_k(P, Q) modulo n, together with Q^k
+   mod n, by doubling and adding along the bits of k. With D = P^2 - 4Q:
+   U_2k = U_k V_k, V_2k = V_k^2 - 2Q^k, U_k+1 = (P U_k + V_k) / 2 and
+   V_k+1 = (D U_k + P V_k) / 2.
+   @param p parameter P.
+   @param q parameter Q.
+   @param k index.
+   @param n odd modular base.
+*/
+pub fn lucas_sequence(p: i64, q: i64, k: u64, n: i64) -> (i64, i64, i64) {
+    let n = n as i128;
+    let (p, q) = ((p as i128).rem_euclid(n), (q as i128).rem_euclid(n));
+    let d = (p * p - 4 * q).rem_euclid(n);
+    let half = |x: i128| if x % 2 == 0 { x / 2 } else { (x + n) / 2 };
+    let (mut u, mut v, mut qk) = (0, 2 % n, 1 % n);
+    for i in (0..u64::BITS - k.leading_zeros()).rev() {
+        u = u * v % n;
+        v = (v * v - 2 * qk).rem_euclid(n);
+        qk = qk * qk % n;
+        if (k >> i) & 1 == 1 {
+            (u, v) = (half((p * u + v) % n), half((d * u % n + p * v) % n));
+            qk = qk * q % n;
+        }
+    }
+    (u as i64, v as i64, qk as i64)
+}
+
+/* Selfridge's parameters for the strong Lucas test: first D in
+   5, -7, 9, -11, ... such that (D/n) = -1, with P = 1 and
+   Q = (1 - D) / 4. None when some D reveals a factor of n.
+   @param n odd integer, not a perfect square.
+*/
+fn selfridge_parameters(n: i64) -> Option<(i64, i64, i64)> {
+    let mut d: i64 = 5;
+    loop {
+        match jacobi(d, n) {
+            -1 => return Some((d, 1, (1 - d) / 4)),
+            0 if d.abs() != n => return None,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+}
+
+/* Strong Lucas probable prime test with Selfridge's parameters: writing
+   n + 1 = d * 2^s with d odd, n passes when U_d = 0 or V_(d * 2^r) = 0
+   mod n for some r < s.
+   @param n odd integer greater than 2.
+*/
+pub fn is_strong_lucas_pseudo_prime(n: i64) -> bool {
+    let root = n.isqrt();
+    if root * root == n {
+        return false;
+    }
+    let Some((_, p, q)) = selfridge_parameters(n) else {
+        return false;
+    };
+    let s = (n as u64 + 1).trailing_zeros();
+    let d = (n as u64 + 1) >> s;
+    let (u, mut v, mut qk) = lucas_sequence(p, q, d, n);
+    if u == 0 || v == 0 {
+        return true;
+    }
+    for _ in 1..s {
+        v = modulo(mul_mod(v, v, n) - mul_mod(2, qk, n), n);
+        qk = mul_mod(qk, qk, n);
+        if v == 0 {
+            return true;
+        }
+    }
+    false
+}
+
+/* Baillie-PSW primality test: strong probable prime to base 2 and
+   strong Lucas probable prime. No composite passing both is known.
+   @param n tested integer
+*/
+pub fn baillie_psw(n: i64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    if n == 2 {
+        return true;
+    }
+    if modulo(n, 2) == 0 {
+        return false;
+    }
+    is_strong_pseudo_prime(n, 2) && is_strong_lucas_pseudo_prime(n)
+}
+
 // ========================= TESTING =========================
 
 pub fn test_is_prime() {
@@ -81,9 +264,245 @@ pub fn test_is_pseudo_prime() {
     }
 }
 
+pub fn test_is_euler_pseudo_prime() {
+    let cases = vec![
+        ((11, vec![2, 4, 5, 10]), true),
+        ((1000000007, vec![2, 3, 5, 7]), true),
+        ((561, vec![2]), true),
+        ((1729, vec![2, 5, 23]), true),
+        ((15, vec![2]), false),
+        ((21, vec![2, 5]), false),
+        ((11, vec![22, -11, 2]), true),
+        ((21, vec![42, 7]), false),
+    ];
+
+    for ele in cases {
+        let result = is_euler_pseudo_prime(ele.0.0, &ele.0.1);
+        if result == ele.1 {
+            println!(
+                "is_euler_pseudo_prime({},{:?})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "is_euler_pseudo_prime({},{:?})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+/* Carmichael numbers pass Fermat's test to every base coprime to them
+   but not Euler's. Bases sharing a factor with n are left out, so that
+   failures come from Euler's criterion alone.
+*/
+pub fn test_solovay_strassen() {
+    let mut rng = SplitMix64::new(38);
+
+    for n in [561, 1105, 1729, 2465, 2821, 6601, 8911, 41041, 825265] {
+        let bases: Vec<i64> = eratosthenes(37)
+            .into_iter()
+            .filter(|a| bezout(*a, n).2 == 1)
+            .collect();
+        let fermat = is_pseudo_prime(n, &bases);
+        let euler = is_euler_pseudo_prime(n, &bases);
+        let result = solovay_strassen(n, 20, &mut rng);
+        if fermat && !euler && !result {
+            println!("solovay_strassen({n})={result} (fermat {fermat}, euler {euler}) passed");
+        } else {
+            println!(
+                "solovay_strassen({n})={result} (fermat {fermat}, euler {euler}) error: \
+                 expected false (fermat true, euler false)"
+            );
+        }
+    }
+
+    let result = (0..5000).all(|n| solovay_strassen(n, 20, &mut rng) == (n >= 2 && is_prime(n)));
+    if result {
+        println!("solovay_strassen agrees with is_prime below 5000 passed");
+    } else {
+        println!("solovay_strassen agrees with is_prime below 5000 error");
+    }
+
+    for n in [1000000007, 2305843009213693951, 9223372036854775783] {
+        let result = solovay_strassen(n, 20, &mut rng);
+        if result {
+            println!("solovay_strassen({n})={result} passed");
+        } else {
+            println!("solovay_strassen({n})={result} error: expected true");
+        }
+    }
+}
+
+pub fn test_is_strong_pseudo_prime() {
+    let cases = vec![
+        ((2047, 2), true),
+        ((2047, 3), false),
+        ((561, 2), false),
+        ((3215031751, 7), true),
+        ((3215031751, 11), false),
+        ((1000000007, 5), true),
+    ];
+
+    for ele in cases {
+        let result = is_strong_pseudo_prime(ele.0.0, ele.0.1);
+        if result == ele.1 {
+            println!(
+                "is_strong_pseudo_prime({},{})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "is_strong_pseudo_prime({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_miller_rabin() {
+    let cases = vec![
+        (0, false),
+        (1, false),
+        (2, true),
+        (37, true),
+        (561, false),
+        (41041, false),
+        (1000000007, true),
+        (3215031751, false),
+        (2305843009213693951, true),
+        (3825123056546413051, false),
+        (9223372036854775783, true),
+        (i64::MAX, false),
+    ];
+
+    for ele in cases {
+        let result = miller_rabin(ele.0);
+        if result == ele.1 {
+            println!("miller_rabin({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "miller_rabin({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+
+    let result = (0..20000).all(|n| miller_rabin(n) == is_prime(n) || n < 2);
+    if result {
+        println!("miller_rabin agrees with is_prime below 20000 passed");
+    } else {
+        println!("miller_rabin agrees with is_prime below 20000 error");
+    }
+
+    let fooled = is_pseudo_prime(561, &[2, 5, 7]);
+    if fooled && !miller_rabin(561) {
+        println!("561 fools is_pseudo_prime but not miller_rabin passed");
+    } else {
+        println!("561 fools is_pseudo_prime but not miller_rabin error");
+    }
+}
+
+pub fn test_lucas_sequence() {
+    let cases = vec![
+        ((1, -1, 10, 1000), (55, 123, 1)),
+        ((1, -1, 90, 1000000007), (210345902, 300062631, 1)),
+        ((3, 2, 20, 1000003), (48572, 48574, 48573)),
+    ];
+
+    for ele in cases {
+        let (p, q, k, n) = ele.0;
+        let result = lucas_sequence(p, q, k, n);
+        if result == ele.1 {
+            println!("lucas_sequence({p},{q},{k},{n})={result:?} passed");
+        } else {
+            println!(
+                "lucas_sequence({p},{q},{k},{n})={result:?} error: expected {:?}",
+                ele.1
+            );
+        }
+    }
+}
+
+pub fn test_is_strong_lucas_pseudo_prime() {
+    let cases = vec![
+        (5459, true),
+        (5777, true),
+        (10877, true),
+        (16109, true),
+        (18971, true),
+        (2047, false),
+        (3277, false),
+        (121, false),
+        (1000000007, true),
+    ];
+
+    for ele in cases {
+        let result = is_strong_lucas_pseudo_prime(ele.0);
+        if result == ele.1 {
+            println!("is_strong_lucas_pseudo_prime({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "is_strong_lucas_pseudo_prime({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_baillie_psw() {
+    let cases = vec![
+        (1, false),
+        (2, true),
+        (3, true),
+        (5, true),
+        (561, false),
+        (2047, false),
+        (5459, false),
+        (3215031751, false),
+        (2305843009213693951, true),
+        (3825123056546413051, false),
+        (9223372036854775783, true),
+        (i64::MAX, false),
+    ];
+
+    for ele in cases {
+        let result = baillie_psw(ele.0);
+        if result == ele.1 {
+            println!("baillie_psw({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "baillie_psw({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+
+    let result = (2..100000).all(|n| baillie_psw(n) == is_prime(n));
+    if result {
+        println!("baillie_psw agrees with is_prime below 100000 passed");
+    } else {
+        println!("baillie_psw agrees with is_prime below 100000 error");
+    }
+}
+
 pub fn test_test_primes() {
     test_is_prime();
     println!();
     test_is_pseudo_prime();
     println!();
+    test_is_euler_pseudo_prime();
+    println!();
+    test_solovay_strassen();
+    println!();
+    test_is_strong_pseudo_prime();
+    println!();
+    test_miller_rabin();
+    println!();
+    test_lucas_sequence();
+    println!();
+    test_is_strong_lucas_pseudo_prime();
+    println!();
+    test_baillie_psw();
+    println!();
 }
@@ -0,0 +1,121 @@
+/********** HKDF **********/
+
+use super::{
+    HashFunction,
+    hmac::{Hmac, hmac},
+};
+
+/* HKDF extraction step, RFC 5869. Concentrates the entropy of input
+   keying material into a pseudorandom key.
+   @param hash underlying hash function.
+   @param salt optional salt, empty meaning a string of hLen zeros.
+   @param ikm input keying material, e.g. a Diffie-Hellman shared secret.
+*/
+pub fn hkdf_extract(hash: HashFunction, salt: &[u8], ikm: &[u8]) -> Vec<u8> {
+    if salt.is_empty() {
+        hmac(hash, &vec![0; hash.output_len()], ikm)
+    } else {
+        hmac(hash, salt, ikm)
+    }
+}
+
+/* HKDF expansion step, RFC 5869. Derives len bytes of output keying
+   material bound to the context info.
+   @param hash underlying hash function.
+   @param prk pseudorandom key, usually output of `hkdf_extract`.
+   @param info context and application specific information.
+   @param len length of output, at most 255 * hLen.
+*/
+pub fn hkdf_expand(hash: HashFunction, prk: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    if len > 255 * hash.output_len() {
+        panic!("len must be <= 255 * hLen");
+    }
+    let mut result = Vec::with_capacity(len + hash.output_len());
+    let mut block: Vec<u8> = vec![];
+    let mut counter = 1u8;
+    while result.len() < len {
+        let mut mac = Hmac::new(hash, prk);
+        mac.update(&block);
+        mac.update(info);
+        mac.update(&[counter]);
+        block = mac.finalize();
+        result.extend_from_slice(&block);
+        counter = counter.wrapping_add(1);
+    }
+    result.truncate(len);
+    result
+}
+
+/* Extract then expand, RFC 5869.
+   @param hash underlying hash function.
+   @param salt optional salt.
+   @param ikm input keying material.
+   @param info context and application specific information.
+   @param len length of output, at most 255 * hLen.
+*/
+pub fn hkdf(hash: HashFunction, salt: &[u8], ikm: &[u8], info: &[u8], len: usize) -> Vec<u8> {
+    hkdf_expand(hash, &hkdf_extract(hash, salt, ikm), info, len)
+}
+
+// ========================= TESTING =========================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/* RFC 5869 test cases 1, 3 and 4. */
+pub fn test_hkdf() {
+    let cases = vec![
+        (
+            (
+                HashFunction::Sha256,
+                vec![0x0b; 22],
+                (0x00..=0x0c).collect::<Vec<u8>>(),
+                (0xf0..=0xf9).collect::<Vec<u8>>(),
+            ),
+            (
+                "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5",
+                "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+            ),
+        ),
+        (
+            (HashFunction::Sha256, vec![0x0b; 22], vec![], vec![]),
+            (
+                "19ef24a32c717b167f33a91d6f648bdf96596776afdb6377ac434c1c293ccb04",
+                "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8",
+            ),
+        ),
+        (
+            (
+                HashFunction::Sha1,
+                vec![0x0b; 11],
+                (0x00..=0x0c).collect(),
+                (0xf0..=0xf9).collect(),
+            ),
+            (
+                "9b6c18c432a7bf8f0e71c8eb88f4b30baa2ba243",
+                "085a01ea1b10f36933068b56efa5ad81a4f14b822f5b091568a9cdd4f155fda2c22e422478d305f3f896",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let ((hash, ikm, salt, info), exp) = ele;
+        let prk = hkdf_extract(hash, &salt, &ikm);
+        let okm = hkdf_expand(hash, &prk, &info, 42);
+        let result = (to_hex(&prk), to_hex(&okm));
+        if result.0 == exp.0 && result.1 == exp.1 && okm == hkdf(hash, &salt, &ikm, &info, 42) {
+            println!("hkdf({hash:?},{} bytes ikm)={} passed", ikm.len(), result.1);
+        } else {
+            println!(
+                "hkdf({hash:?},{} bytes ikm)={result:?} error: expected {exp:?}",
+                ikm.len()
+            );
+        }
+    }
+}
+
+pub fn test_hkdfs() {
+    test_hkdf();
+    println!();
+}
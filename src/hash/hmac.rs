@@ -0,0 +1,188 @@
+/********** HMAC **********/
+
+use super::{HashFunction, Hasher};
+
+/* Streaming HMAC, RFC 2104. Message is fed through any number of
+   `update` calls, `finalize` gives back the authentication tag.
+*/
+#[derive(Clone, Debug)]
+pub struct Hmac {
+    inner: Hasher,
+    outer: Hasher,
+}
+
+impl Hmac {
+    /* New HMAC computation.
+       @param hash underlying hash function.
+       @param key secret key, hashed first when longer than a block.
+    */
+    pub fn new(hash: HashFunction, key: &[u8]) -> Hmac {
+        let mut block = if key.len() > hash.block_len() {
+            hash.digest(key)
+        } else {
+            key.to_vec()
+        };
+        block.resize(hash.block_len(), 0);
+
+        let mut inner = hash.hasher();
+        inner.update(&block.iter().map(|b| b ^ 0x36).collect::<Vec<u8>>());
+        let mut outer = hash.hasher();
+        outer.update(&block.iter().map(|b| b ^ 0x5c).collect::<Vec<u8>>());
+        Hmac { inner, outer }
+    }
+
+    /* Feed the next bytes of the message.
+       @param data bytes to authenticate.
+    */
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /* Give back the authentication tag of the message. */
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = self.outer;
+        outer.update(&self.inner.finalize());
+        outer.finalize()
+    }
+}
+
+/* One-shot HMAC of a message.
+   @param hash underlying hash function.
+   @param key secret key.
+   @param data message to authenticate.
+*/
+pub fn hmac(hash: HashFunction, key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::new(hash, key);
+    mac.update(data);
+    mac.finalize()
+}
+
+/* Check an HMAC tag. Comparison goes through the whole tag whatever the
+   position of the first difference.
+   @param hash underlying hash function.
+   @param key secret key.
+   @param data authenticated message.
+   @param tag tag to check.
+*/
+pub fn hmac_verify(hash: HashFunction, key: &[u8], data: &[u8], tag: &[u8]) -> bool {
+    let expected = hmac(hash, key, data);
+    if expected.len() != tag.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(tag)
+        .fold(0, |acc, (a, b)| acc | (a ^ b))
+        == 0
+}
+
+// ========================= TESTING =========================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/* RFC 4231 test cases 1, 2, 3 and 6 for SHA-256, RFC 2202 test cases 1
+   and 2 for SHA-1.
+*/
+pub fn test_hmac() {
+    let cases = vec![
+        (
+            (HashFunction::Sha256, vec![0x0b; 20], b"Hi There".to_vec()),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+        ),
+        (
+            (
+                HashFunction::Sha256,
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+            ),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        ),
+        (
+            (HashFunction::Sha256, vec![0xaa; 20], vec![0xdd; 50]),
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+        ),
+        (
+            (
+                HashFunction::Sha256,
+                vec![0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec(),
+            ),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ),
+        (
+            (HashFunction::Sha1, vec![0x0b; 20], b"Hi There".to_vec()),
+            "b617318655057264e28bc0b6fb378c8ef146be00",
+        ),
+        (
+            (
+                HashFunction::Sha1,
+                b"Jefe".to_vec(),
+                b"what do ya want for nothing?".to_vec(),
+            ),
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+        ),
+    ];
+
+    for ele in cases {
+        let ((hash, key, data), exp) = ele;
+        let result = to_hex(&hmac(hash, &key, &data));
+        if result == exp {
+            println!("hmac({hash:?},{} bytes key)={result} passed", key.len());
+        } else {
+            println!(
+                "hmac({hash:?},{} bytes key)={result} error: expected {exp}",
+                key.len()
+            );
+        }
+    }
+}
+
+pub fn test_hmac_verify() {
+    let tag = hmac(
+        HashFunction::Sha256,
+        b"Jefe",
+        b"what do ya want for nothing?",
+    );
+    let mut forged = tag.clone();
+    forged[31] ^= 1;
+    let cases = vec![
+        (
+            (b"what do ya want for nothing?".to_vec(), tag.clone()),
+            true,
+        ),
+        (
+            (b"what do ya want for nothing!".to_vec(), tag.clone()),
+            false,
+        ),
+        ((b"what do ya want for nothing?".to_vec(), forged), false),
+        (
+            (b"what do ya want for nothing?".to_vec(), tag[..16].to_vec()),
+            false,
+        ),
+    ];
+
+    for ele in cases {
+        let ((data, t), exp) = ele;
+        let result = hmac_verify(HashFunction::Sha256, b"Jefe", &data, &t);
+        if result == exp {
+            println!(
+                "hmac_verify({:?})={result} passed",
+                String::from_utf8_lossy(&data)
+            );
+        } else {
+            println!(
+                "hmac_verify({:?})={result} error: expected {exp}",
+                String::from_utf8_lossy(&data)
+            );
+        }
+    }
+}
+
+pub fn test_hmacs() {
+    test_hmac();
+    println!();
+    test_hmac_verify();
+    println!();
+}
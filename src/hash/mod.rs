@@ -0,0 +1,71 @@
+pub mod hkdf;
+pub mod hmac;
+pub mod sha1;
+pub mod sha256;
+
+/* Hash functions available to signature and padding schemes. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashFunction {
+    Sha1,
+    Sha256,
+}
+
+impl HashFunction {
+    /* Size of a digest in bytes. */
+    pub fn output_len(&self) -> usize {
+        match self {
+            HashFunction::Sha1 => 20,
+            HashFunction::Sha256 => 32,
+        }
+    }
+
+    /* Size of a compression block in bytes. */
+    pub fn block_len(&self) -> usize {
+        64
+    }
+
+    /* Digest of a message.
+       @param data message to hash.
+    */
+    pub fn digest(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            HashFunction::Sha1 => sha1::sha1(data).to_vec(),
+            HashFunction::Sha256 => sha256::sha256(data).to_vec(),
+        }
+    }
+
+    /* Fresh streaming hasher. */
+    pub fn hasher(&self) -> Hasher {
+        match self {
+            HashFunction::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            HashFunction::Sha256 => Hasher::Sha256(sha256::Sha256::new()),
+        }
+    }
+}
+
+/* Streaming hasher of any of the available hash functions. */
+#[derive(Clone, Debug)]
+pub enum Hasher {
+    Sha1(sha1::Sha1),
+    Sha256(sha256::Sha256),
+}
+
+impl Hasher {
+    /* Feed the next bytes of the message.
+       @param data bytes to hash.
+    */
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Sha1(h) => h.update(data),
+            Hasher::Sha256(h) => h.update(data),
+        }
+    }
+
+    /* Give back the digest of the message. */
+    pub fn finalize(self) -> Vec<u8> {
+        match self {
+            Hasher::Sha1(h) => h.finalize().to_vec(),
+            Hasher::Sha256(h) => h.finalize().to_vec(),
+        }
+    }
+}
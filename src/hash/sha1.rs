@@ -0,0 +1,188 @@
+/********** SHA-1 **********/
+
+const H0: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+/* SHA-1 compression function on a single 64 bytes block. */
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    let mut w = [0u32; 80];
+    for (i, word) in block.chunks(4).enumerate() {
+        w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (i, wi) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let t = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*wi);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = t;
+    }
+
+    for (s, x) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(x);
+    }
+}
+
+/* Streaming SHA-1 hasher, FIPS 180-4. Message is fed through any number
+   of `update` calls, then `finalize` pads it and gives back the digest.
+*/
+#[derive(Clone, Debug)]
+pub struct Sha1 {
+    state: [u32; 5],
+    buffer: [u8; 64],
+    buffer_len: usize,
+    length: u64,
+}
+
+impl Default for Sha1 {
+    fn default() -> Sha1 {
+        Sha1::new()
+    }
+}
+
+impl Sha1 {
+    pub fn new() -> Sha1 {
+        Sha1 {
+            state: H0,
+            buffer: [0; 64],
+            buffer_len: 0,
+            length: 0,
+        }
+    }
+
+    /* Feed the next bytes of the message.
+       @param data bytes to hash.
+    */
+    pub fn update(&mut self, data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+        let mut data = data;
+        if self.buffer_len > 0 {
+            let take = (64 - self.buffer_len).min(data.len());
+            self.buffer[self.buffer_len..self.buffer_len + take].copy_from_slice(&data[..take]);
+            self.buffer_len += take;
+            data = &data[take..];
+            if self.buffer_len < 64 {
+                return;
+            }
+            compress(&mut self.state, &self.buffer);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            compress(&mut self.state, block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /* Pad the message with its length and give back its digest. */
+    pub fn finalize(mut self) -> [u8; 20] {
+        let bit_len = self.length.wrapping_mul(8);
+        let mut padding = vec![0x80];
+        padding.resize(
+            if self.buffer_len < 56 {
+                56 - self.buffer_len
+            } else {
+                120 - self.buffer_len
+            },
+            0,
+        );
+        padding.extend_from_slice(&bit_len.to_be_bytes());
+        self.update(&padding);
+
+        let mut result = [0; 20];
+        for (chunk, s) in result.chunks_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&s.to_be_bytes());
+        }
+        result
+    }
+}
+
+/* One-shot SHA-1 digest of a message. Broken for collision
+   resistance, only kept for legacy vectors.
+   @param data message to hash.
+*/
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut hasher = Sha1::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+// ========================= TESTING =========================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn test_sha1() {
+    let cases = vec![
+        ("", "da39a3ee5e6b4b0d3255bfef95601890afd80709"),
+        ("abc", "a9993e364706816aba3e25717850c26c9cd0d89d"),
+        (
+            "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1",
+        ),
+        (
+            "abcdefghbcdefghicdefghijdefghijkefghijklfghijklmghijklmnhijklmnoijklmnopjklmnopqklmnopqrlmnopqrsmnopqrstnopqrstu",
+            "a49b2446a02c645bf419f995b67091253a04a259",
+        ),
+    ];
+
+    for ele in cases {
+        let result = to_hex(&sha1(ele.0.as_bytes()));
+        if result == ele.1 {
+            println!("sha1({:?})={} passed", ele.0, result);
+        } else {
+            println!("sha1({:?})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+/* One million repetitions of 'a' fed in chunks of various sizes, NIST
+   long message example.
+*/
+pub fn test_sha1_streaming() {
+    let expected = "34aa973cd4c4daa4f61eeb2bdbad27316534016f";
+    let cases = vec![1_000_000, 1, 55, 56, 63, 64, 65, 1000];
+
+    for chunk in cases {
+        let mut hasher = Sha1::new();
+        let mut remaining = 1_000_000;
+        while remaining > 0 {
+            let len = chunk.min(remaining);
+            hasher.update(&vec![b'a'; len]);
+            remaining -= len;
+        }
+        let result = to_hex(&hasher.finalize());
+        if result == expected {
+            println!("Sha1 streaming a^1000000 by chunks of {chunk} passed");
+        } else {
+            println!(
+                "Sha1 streaming a^1000000 by chunks of {chunk}={result} error: expected {expected}"
+            );
+        }
+    }
+}
+
+pub fn test_hash() {
+    test_sha1();
+    println!();
+    test_sha1_streaming();
+    println!();
+}
@@ -0,0 +1,85 @@
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Mul, Sub};
+
+use crate::builtin;
+use crate::scalable::{self, scalables::Scalable};
+
+/* Operations shared by builtin integers and scalable integers.
+   Lets code such as key types be written once for both, each method
+   forwarding to the corresponding function of the builtin or scalable
+   modules.
+*/
+pub trait Integer:
+    Clone + Ord + Debug + Display + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    fn from_i64(x: i64) -> Self;
+
+    /* Number of bits of the absolute value, 0 for 0. */
+    fn bits(&self) -> usize;
+
+    /* Euclidean modulo, see `builtins::modulo`. */
+    fn modulo(&self, m: &Self) -> Self;
+
+    /* Modular exponentiation, see `power::mod_power`. */
+    fn mod_power(&self, n: &Self, m: &Self) -> Self;
+
+    /* Bezout coefficients, see `basic_arithmetics::bezout`. */
+    fn bezout(&self, other: &Self) -> (Self, Self, Self);
+
+    fn zero() -> Self {
+        Self::from_i64(0)
+    }
+
+    fn one() -> Self {
+        Self::from_i64(1)
+    }
+
+    /* Number of bytes needed to write the absolute value. */
+    fn bytes(&self) -> usize {
+        self.bits().div_ceil(8)
+    }
+}
+
+impl Integer for i64 {
+    fn from_i64(x: i64) -> i64 {
+        x
+    }
+
+    fn bits(&self) -> usize {
+        (u64::BITS - self.unsigned_abs().leading_zeros()) as usize
+    }
+
+    fn modulo(&self, m: &i64) -> i64 {
+        builtin::builtins::modulo(*self, *m)
+    }
+
+    fn mod_power(&self, n: &i64, m: &i64) -> i64 {
+        builtin::power::mod_power(*self, *n, *m)
+    }
+
+    fn bezout(&self, other: &i64) -> (i64, i64, i64) {
+        builtin::basic_arithmetics::bezout(*self, *other)
+    }
+}
+
+impl Integer for Scalable {
+    fn from_i64(x: i64) -> Scalable {
+        Scalable::from(x)
+    }
+
+    fn bits(&self) -> usize {
+        Scalable::bits(self)
+    }
+
+    fn modulo(&self, m: &Scalable) -> Scalable {
+        scalable::scalables::modulo(self, m)
+    }
+
+    fn mod_power(&self, n: &Scalable, m: &Scalable) -> Scalable {
+        scalable::scalable_power::mod_power(self, n, m)
+    }
+
+    fn bezout(&self, other: &Scalable) -> (Scalable, Scalable, Scalable) {
+        scalable::scalable_basic_arithmetics::bezout(self, other)
+    }
+}
@@ -0,0 +1,432 @@
+use std::fmt;
+
+use crate::builtin::chinese_remainder::{crt_coefficients, inverse};
+use crate::integer::Integer;
+use crate::scalable::scalables::Scalable;
+
+/* Reasons for which key material is rejected on construction. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KeyError {
+    ModulusTooSmall,
+    InvalidExponent,
+    MismatchedExponents,
+    InvalidGenerator,
+    InvalidPublicValue,
+    TooFewPrimes,
+    RepeatedPrime,
+}
+
+impl fmt::Display for KeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let msg = match self {
+            KeyError::ModulusTooSmall => "modulus is too small",
+            KeyError::InvalidExponent => "exponent is out of range",
+            KeyError::MismatchedExponents => "private exponent does not invert public exponent",
+            KeyError::InvalidGenerator => "generator is out of range",
+            KeyError::InvalidPublicValue => "public value is out of range",
+            KeyError::TooFewPrimes => "at least two primes are needed",
+            KeyError::RepeatedPrime => "primes must be distinct and pairwise coprime",
+        };
+        write!(f, "{}", msg)
+    }
+}
+
+impl std::error::Error for KeyError {}
+
+/********** RSA keys **********/
+
+/* Public part (n, e) of an RSA key. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPublicKey<T> {
+    n: T,
+    e: T,
+}
+
+/* Private part of an RSA key. Public exponent e is kept next to
+   private exponent d so that the public key can be derived back.
+   When the prime factors r1, ..., rk of n are known they are kept along
+   with their CRT exponents d mod (ri - 1) and coefficients, as in RFC
+   8017, so that decryption can work modulo each prime.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPrivateKey<T> {
+    n: T,
+    e: T,
+    d: T,
+    primes: Vec<T>,
+    exponents: Vec<T>,
+    coefficients: Vec<T>,
+}
+
+/* Couple of matching public and private RSA keys. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaKeyPair<T> {
+    pub public: RsaPublicKey<T>,
+    pub private: RsaPrivateKey<T>,
+}
+
+impl<T: Integer> RsaPublicKey<T> {
+    /* Build a public key checking that n > 2 and e is in ]1, n[.
+       @param n modulus
+       @param e public exponent
+    */
+    pub fn new(n: T, e: T) -> Result<RsaPublicKey<T>, KeyError> {
+        if n <= T::from_i64(2) {
+            return Err(KeyError::ModulusTooSmall);
+        }
+        if e <= T::one() || e >= n {
+            return Err(KeyError::InvalidExponent);
+        }
+        Ok(RsaPublicKey { n, e })
+    }
+
+    pub fn n(&self) -> &T {
+        &self.n
+    }
+
+    pub fn e(&self) -> &T {
+        &self.e
+    }
+
+    /* Size of the modulus in bits. */
+    pub fn size_bits(&self) -> usize {
+        self.n.bits()
+    }
+
+    /* Size of the modulus in bytes. */
+    pub fn size_bytes(&self) -> usize {
+        self.n.bytes()
+    }
+}
+
+impl<T: Integer> RsaPrivateKey<T> {
+    /* Build a private key. On top of the public key checks, d must lie
+       in ]0, n[ and undo e on a sample value.
+       @param n modulus
+       @param e public exponent
+       @param d private exponent
+    */
+    pub fn new(n: T, e: T, d: T) -> Result<RsaPrivateKey<T>, KeyError> {
+        let public = RsaPublicKey::new(n, e)?;
+        let (n, e) = (public.n, public.e);
+        if d <= T::zero() || d >= n {
+            return Err(KeyError::InvalidExponent);
+        }
+        let sample = T::from_i64(2);
+        if sample.mod_power(&e, &n).mod_power(&d, &n) != sample {
+            return Err(KeyError::MismatchedExponents);
+        }
+        Ok(RsaPrivateKey {
+            n,
+            e,
+            d,
+            primes: vec![],
+            exponents: vec![],
+            coefficients: vec![],
+        })
+    }
+
+    /* Build a private key from k >= 2 distinct primes, multi-prime RSA
+       when k > 2. Private exponent is the inverse of e modulo the product
+       of the ri - 1. Factors sharing a divisor are rejected before the CRT
+       coefficients, which need them pairwise coprime, are computed.
+       @param primes distinct primes r1, ..., rk whose product is n.
+       @param e public exponent
+    */
+    pub fn from_primes(primes: Vec<T>, e: T) -> Result<RsaPrivateKey<T>, KeyError> {
+        if primes.len() < 2 {
+            return Err(KeyError::TooFewPrimes);
+        }
+        for (i, r) in primes.iter().enumerate() {
+            if primes[..i].iter().any(|s| r.bezout(s).2 != T::one()) {
+                return Err(KeyError::RepeatedPrime);
+            }
+        }
+        let mut n = T::one();
+        let mut phi = T::one();
+        for r in &primes {
+            n = n * r.clone();
+            phi = phi * (r.clone() - T::one());
+        }
+        let d = match inverse(&e, &phi) {
+            Some(d) => d,
+            None => return Err(KeyError::InvalidExponent),
+        };
+        let mut key = RsaPrivateKey::new(n, e, d)?;
+        key.exponents = primes
+            .iter()
+            .map(|r| key.d.modulo(&(r.clone() - T::one())))
+            .collect();
+        key.coefficients = crt_coefficients(&primes);
+        key.primes = primes;
+        Ok(key)
+    }
+
+    pub fn n(&self) -> &T {
+        &self.n
+    }
+
+    pub fn d(&self) -> &T {
+        &self.d
+    }
+
+    /* Prime factors of n, empty when unknown. */
+    pub fn primes(&self) -> &[T] {
+        &self.primes
+    }
+
+    /* CRT exponents d mod (ri - 1), one per prime. */
+    pub fn exponents(&self) -> &[T] {
+        &self.exponents
+    }
+
+    /* CRT coefficients, see `chinese_remainder::crt_coefficients`. */
+    pub fn coefficients(&self) -> &[T] {
+        &self.coefficients
+    }
+
+    /* Public key matching this private key. */
+    pub fn public_key(&self) -> RsaPublicKey<T> {
+        RsaPublicKey {
+            n: self.n.clone(),
+            e: self.e.clone(),
+        }
+    }
+
+    /* Size of the modulus in bits. */
+    pub fn size_bits(&self) -> usize {
+        self.n.bits()
+    }
+
+    /* Size of the modulus in bytes. */
+    pub fn size_bytes(&self) -> usize {
+        self.n.bytes()
+    }
+}
+
+impl<T: Integer> RsaKeyPair<T> {
+    /* Key pair from a valid private key. */
+    pub fn from_private(private: RsaPrivateKey<T>) -> RsaKeyPair<T> {
+        RsaKeyPair {
+            public: private.public_key(),
+            private,
+        }
+    }
+}
+
+/********** ElGamal keys **********/
+
+/* Public ElGamal key: public data (g, p) and h = g^a mod p. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElGamalPublicKey<T> {
+    p: T,
+    g: T,
+    h: T,
+}
+
+/* Private ElGamal key: public data (g, p) and secret exponent a. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ElGamalPrivateKey<T> {
+    p: T,
+    g: T,
+    a: T,
+}
+
+fn check_public_data<T: Integer>(p: &T, g: &T) -> Result<(), KeyError> {
+    if *p <= T::from_i64(2) {
+        return Err(KeyError::ModulusTooSmall);
+    }
+    if *g <= T::one() || g >= p {
+        return Err(KeyError::InvalidGenerator);
+    }
+    Ok(())
+}
+
+impl<T: Integer> ElGamalPublicKey<T> {
+    /* Build a public key checking that p > 2, g is in ]1, p[ and h in
+       ]0, p[.
+       @param p prime modulus
+       @param g generator of the multiplicative group
+       @param h public value g^a mod p
+    */
+    pub fn new(p: T, g: T, h: T) -> Result<ElGamalPublicKey<T>, KeyError> {
+        check_public_data(&p, &g)?;
+        if h <= T::zero() || h >= p {
+            return Err(KeyError::InvalidPublicValue);
+        }
+        Ok(ElGamalPublicKey { p, g, h })
+    }
+
+    pub fn p(&self) -> &T {
+        &self.p
+    }
+
+    pub fn g(&self) -> &T {
+        &self.g
+    }
+
+    pub fn h(&self) -> &T {
+        &self.h
+    }
+
+    /* Size of the prime modulus in bits. */
+    pub fn size_bits(&self) -> usize {
+        self.p.bits()
+    }
+
+    /* Size of the prime modulus in bytes. */
+    pub fn size_bytes(&self) -> usize {
+        self.p.bytes()
+    }
+}
+
+impl<T: Integer> ElGamalPrivateKey<T> {
+    /* Build a private key checking public data as for the public key and
+       that a is in ]0, p - 1[.
+       @param p prime modulus
+       @param g generator of the multiplicative group
+       @param a secret exponent
+    */
+    pub fn new(p: T, g: T, a: T) -> Result<ElGamalPrivateKey<T>, KeyError> {
+        check_public_data(&p, &g)?;
+        if a <= T::zero() || a >= p.clone() - T::one() {
+            return Err(KeyError::InvalidExponent);
+        }
+        Ok(ElGamalPrivateKey { p, g, a })
+    }
+
+    pub fn p(&self) -> &T {
+        &self.p
+    }
+
+    pub fn g(&self) -> &T {
+        &self.g
+    }
+
+    pub fn a(&self) -> &T {
+        &self.a
+    }
+
+    /* Public key matching this private key. */
+    pub fn public_key(&self) -> ElGamalPublicKey<T> {
+        ElGamalPublicKey {
+            p: self.p.clone(),
+            g: self.g.clone(),
+            h: self.g.mod_power(&self.a, &self.p),
+        }
+    }
+
+    /* Size of the prime modulus in bits. */
+    pub fn size_bits(&self) -> usize {
+        self.p.bits()
+    }
+
+    /* Size of the prime modulus in bytes. */
+    pub fn size_bytes(&self) -> usize {
+        self.p.bytes()
+    }
+}
+
+// ========================= TESTING =========================
+
+pub fn test_rsa_private_key() {
+    let cases = vec![
+        ((99400891, 36199003, 30869683), Ok(27)),
+        (
+            (99400891, 36199003, 30869684),
+            Err(KeyError::MismatchedExponents),
+        ),
+        (
+            (99400891, 99400891, 30869683),
+            Err(KeyError::InvalidExponent),
+        ),
+        ((99400891, 36199003, 0), Err(KeyError::InvalidExponent)),
+        ((2, 1, 1), Err(KeyError::ModulusTooSmall)),
+    ];
+
+    for ele in cases {
+        let ((n, e, d), exp) = ele;
+        let result = RsaPrivateKey::new(n, e, d).map(|k| k.size_bits());
+        if result == exp {
+            println!("RsaPrivateKey::new({n},{e},{d})={result:?} passed");
+        } else {
+            println!("RsaPrivateKey::new({n},{e},{d})={result:?} error: expected {exp:?}");
+        }
+    }
+
+    let n = Scalable::from_str_radix("340282366920938463463374607431768211457", 10).unwrap();
+    let result = RsaPublicKey::new(n.clone(), Scalable::from(65537i64)).map(|k| k.size_bytes());
+    if result == Ok(17) {
+        println!("RsaPublicKey::new({n},65537)={result:?} passed");
+    } else {
+        println!("RsaPublicKey::new({n},65537)={result:?} error: expected Ok(17)");
+    }
+}
+
+pub fn test_rsa_public_key() {
+    let cases = vec![(99400891, 36199003, 30869683)];
+
+    for ele in cases {
+        let (n, e, d) = ele;
+        let result = RsaPrivateKey::new(n, e, d).unwrap().public_key();
+        if result == RsaPublicKey::new(n, e).unwrap() {
+            println!("public_key({n},{e},{d})={result:?} passed");
+        } else {
+            println!("public_key({n},{e},{d})={result:?} error: expected ({n},{e})");
+        }
+    }
+}
+
+pub fn test_rsa_from_primes() {
+    let cases = vec![
+        ((vec![61, 53], 17), Ok((3233, 2753))),
+        ((vec![11, 13, 17], 7), Ok((2431, 823))),
+        ((vec![61], 17), Err(KeyError::TooFewPrimes)),
+        ((vec![61, 53, 61], 17), Err(KeyError::RepeatedPrime)),
+        ((vec![53, 53], 17), Err(KeyError::RepeatedPrime)),
+        ((vec![5, 15], 17), Err(KeyError::RepeatedPrime)),
+        ((vec![61, 53], 15), Err(KeyError::InvalidExponent)),
+    ];
+
+    for ele in cases {
+        let ((primes, e), exp) = ele;
+        let result = RsaPrivateKey::from_primes(primes.clone(), e).map(|k| (*k.n(), *k.d()));
+        if result == exp {
+            println!("RsaPrivateKey::from_primes({primes:?},{e})={result:?} passed");
+        } else {
+            println!(
+                "RsaPrivateKey::from_primes({primes:?},{e})={result:?} error: expected {exp:?}"
+            );
+        }
+    }
+}
+
+pub fn test_elgamal_keys() {
+    let cases = vec![
+        ((23, 5, 6), Ok(8)),
+        ((23, 5, 22), Err(KeyError::InvalidExponent)),
+        ((23, 23, 6), Err(KeyError::InvalidGenerator)),
+        ((2, 1, 1), Err(KeyError::ModulusTooSmall)),
+    ];
+
+    for ele in cases {
+        let ((p, g, a), exp) = ele;
+        let result = ElGamalPrivateKey::new(p, g, a).map(|k| *k.public_key().h());
+        if result == exp {
+            println!("ElGamalPrivateKey::new({p},{g},{a})={result:?} passed");
+        } else {
+            println!("ElGamalPrivateKey::new({p},{g},{a})={result:?} error: expected {exp:?}");
+        }
+    }
+}
+
+pub fn test_keys() {
+    test_rsa_private_key();
+    println!();
+    test_rsa_public_key();
+    println!();
+    test_rsa_from_primes();
+    println!();
+    test_elgamal_keys();
+    println!();
+}
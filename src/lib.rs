@@ -1,2 +1,6 @@
 pub mod builtin;
+pub mod hash;
+pub mod integer;
+pub mod keys;
+pub mod rng;
 pub mod scalable;
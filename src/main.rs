@@ -1,4 +1,4 @@
-use afit_rs::builtin;
+use afit_rs::{builtin, scalable};
 
 fn main() {
     /*
@@ -12,7 +12,35 @@ fn main() {
     println!();
     builtin::generate_primes::test_generate_primes();
     println!();
+    builtin::chinese_remainder::test_chinese_remainders();
+    println!();
+    scalable::scalables::test_scalables();
+    println!();
+    scalable::scalable_basic_arithmetics::test_basic_arithmetics();
+    println!();
+    scalable::scalable_power::test_powers();
+    println!();
+    scalable::scalable_test_primes::test_test_primes();
+    println!();
+    scalable::scalable_generate_primes::test_generate_primes();
+    println!();
+    scalable::scalable_certificates::test_certificates();
+    println!();
+    afit_rs::keys::test_keys();
+    println!();
+    afit_rs::hash::sha1::test_hash();
+    println!();
+    afit_rs::hash::sha256::test_hash();
+    println!();
+    afit_rs::hash::hmac::test_hmacs();
+    println!();
+    afit_rs::hash::hkdf::test_hkdfs();
+    println!();
+    afit_rs::rng::test_rngs();
+    println!();
     */
     builtin::ciphers::test_ciphers();
     println!();
+    scalable::scalable_ciphers::test_ciphers();
+    println!();
 }
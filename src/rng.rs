@@ -0,0 +1,333 @@
+use std::fs::File;
+use std::io::{self, Read};
+
+use crate::scalable::scalables::Scalable;
+
+/* Source of random 64 bits words.
+   Every function needing randomness takes a generator implementing this
+   trait so that callers decide where randomness comes from.
+*/
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    /* Fill a buffer with random bytes.
+       @param dest buffer to fill.
+    */
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+}
+
+/* SplitMix64 generator.
+   Deterministic and NOT cryptographically secure, only meant to make
+   tests reproducible.
+*/
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> SplitMix64 {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+}
+
+/********** ChaCha20 **********/
+
+fn quarter_round(s: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(16);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(12);
+    s[a] = s[a].wrapping_add(s[b]);
+    s[d] = (s[d] ^ s[a]).rotate_left(8);
+    s[c] = s[c].wrapping_add(s[d]);
+    s[b] = (s[b] ^ s[c]).rotate_left(7);
+}
+
+/* ChaCha20 block function, RFC 8439 section 2.3.
+   @param key 256 bits key as eight little endian words.
+   @param input counter and nonce words, state words 12 to 15.
+*/
+pub fn chacha20_block(key: &[u32; 8], input: &[u32; 4]) -> [u32; 16] {
+    let mut state = [0u32; 16];
+    state[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+    state[4..12].copy_from_slice(key);
+    state[12..].copy_from_slice(input);
+
+    let mut working = state;
+    for _ in 0..10 {
+        quarter_round(&mut working, 0, 4, 8, 12);
+        quarter_round(&mut working, 1, 5, 9, 13);
+        quarter_round(&mut working, 2, 6, 10, 14);
+        quarter_round(&mut working, 3, 7, 11, 15);
+        quarter_round(&mut working, 0, 5, 10, 15);
+        quarter_round(&mut working, 1, 6, 11, 12);
+        quarter_round(&mut working, 2, 7, 8, 13);
+        quarter_round(&mut working, 3, 4, 9, 14);
+    }
+    for (w, s) in working.iter_mut().zip(state) {
+        *w = w.wrapping_add(s);
+    }
+    working
+}
+
+/* Cryptographically secure generator outputting the ChaCha20 keystream
+   of its 256 bits seed, with a 64 bits block counter and a zero nonce.
+   `new` seeds it from the operating system, `from_seed` gives a
+   deterministic stream for reproducible tests.
+*/
+pub struct ChaCha20Rng {
+    key: [u32; 8],
+    counter: u64,
+    block: [u32; 16],
+    index: usize,
+}
+
+impl ChaCha20Rng {
+    /* Generator seeded with 32 bytes read from /dev/urandom. */
+    pub fn new() -> io::Result<ChaCha20Rng> {
+        let mut seed = [0u8; 32];
+        File::open("/dev/urandom")?.read_exact(&mut seed)?;
+        Ok(ChaCha20Rng::from_seed(seed))
+    }
+
+    /* Deterministic generator, same seed giving same stream.
+       @param seed 256 bits ChaCha20 key.
+    */
+    pub fn from_seed(seed: [u8; 32]) -> ChaCha20Rng {
+        let mut key = [0u32; 8];
+        for (k, chunk) in key.iter_mut().zip(seed.chunks(4)) {
+            *k = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+        ChaCha20Rng {
+            key,
+            counter: 0,
+            block: [0; 16],
+            index: 16,
+        }
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        if self.index == 16 {
+            let input = [self.counter as u32, (self.counter >> 32) as u32, 0, 0];
+            self.block = chacha20_block(&self.key, &input);
+            self.counter = self.counter.wrapping_add(1);
+            self.index = 0;
+        }
+        self.index += 1;
+        self.block[self.index - 1]
+    }
+}
+
+impl Rng for ChaCha20Rng {
+    fn next_u64(&mut self) -> u64 {
+        let low = self.next_u32() as u64;
+        low | ((self.next_u32() as u64) << 32)
+    }
+}
+
+/********** Uniform integers **********/
+
+/* Uniformly random natural number in [0, 2^bits[.
+   @param bits number of random bits.
+   @param rng random generator.
+*/
+pub fn random_bits(bits: usize, rng: &mut impl Rng) -> Scalable {
+    let mut bytes = vec![0; bits.div_ceil(8)];
+    rng.fill_bytes(&mut bytes);
+    if !bits.is_multiple_of(8) {
+        bytes[0] &= (1 << (bits % 8)) - 1;
+    }
+    Scalable::from_bytes_be(&bytes)
+}
+
+/* Uniformly random integer in [0, n[ by rejection sampling: draws as
+   many bits as n - 1 has until the draw falls below n, which takes less
+   than two draws on average.
+   @param n upper bound, greater than 0.
+   @param rng random generator.
+*/
+pub fn uniform_i64(n: i64, rng: &mut impl Rng) -> i64 {
+    if n <= 0 {
+        panic!("n must be > 0");
+    }
+    let bits = u64::BITS - ((n - 1) as u64).leading_zeros();
+    let mask = if bits == 0 {
+        0
+    } else {
+        u64::MAX >> (u64::BITS - bits)
+    };
+    loop {
+        let x = rng.next_u64() & mask;
+        if x < n as u64 {
+            return x as i64;
+        }
+    }
+}
+
+/* Uniformly random natural number in [0, n[ by rejection sampling, see
+   `uniform_i64`.
+   @param n upper bound, greater than 0.
+   @param rng random generator.
+*/
+pub fn uniform_scalable(n: &Scalable, rng: &mut impl Rng) -> Scalable {
+    if n.is_negative() || n.is_zero() {
+        panic!("n must be > 0");
+    }
+    let bits = (n - 1).bits();
+    loop {
+        let x = random_bits(bits, rng);
+        if &x < n {
+            return x;
+        }
+    }
+}
+
+// ========================= TESTING =========================
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/* RFC 8439 section 2.3.2 test vector. */
+pub fn test_chacha20_block() {
+    let mut key = [0u32; 8];
+    for (i, k) in key.iter_mut().enumerate() {
+        let b = 4 * i as u32;
+        *k = u32::from_le_bytes([b as u8, b as u8 + 1, b as u8 + 2, b as u8 + 3]);
+    }
+    let block = chacha20_block(&key, &[1, 0x09000000, 0x4a000000, 0]);
+    let bytes: Vec<u8> = block.iter().flat_map(|w| w.to_le_bytes()).collect();
+    let result = to_hex(&bytes);
+    let exp = "10f1e7e4d13b5915500fdd1fa32071c4c7d1f4c733c068030422aa9ac3d46c4e\
+               d2826446079faa0914c2d705d98b02a2b5129cd1de164eb9cbd083e8a2503c4e";
+    if result == exp {
+        println!("chacha20_block(rfc8439)={result} passed");
+    } else {
+        println!("chacha20_block(rfc8439)={result} error: expected {exp}");
+    }
+}
+
+/* Keystream of the all zero key and of key 00..1f, spanning several
+   blocks to check the counter.
+*/
+pub fn test_chacha20_rng() {
+    let seed: [u8; 32] = core::array::from_fn(|i| i as u8);
+    let cases = vec![
+        (
+            ([0u8; 32], 0, 64),
+            "76b8e0ada0f13d90405d6ae55386bd28bdd219b8a08ded1aa836efcc8b770dc7\
+             da41597c5157488d7724e03fb8d84a376a43b8f41518a11cc387b669b2ee6586",
+        ),
+        (
+            (seed, 128, 32),
+            "42f22ddca74a92d56ca78aef298e723b60237f3647eabeb7f3e09c30ce80e3e2",
+        ),
+    ];
+
+    for ele in cases {
+        let ((seed, skip, len), exp) = ele;
+        let mut rng = ChaCha20Rng::from_seed(seed);
+        let mut bytes = vec![0; skip + len];
+        rng.fill_bytes(&mut bytes);
+        let result = to_hex(&bytes[skip..]);
+        if result == exp {
+            println!(
+                "ChaCha20Rng(seed {:02x}..)[{skip}..]={result} passed",
+                seed[31]
+            );
+        } else {
+            println!(
+                "ChaCha20Rng(seed {:02x}..)[{skip}..]={result} error: expected {exp}",
+                seed[31]
+            );
+        }
+    }
+
+    let mut a = ChaCha20Rng::from_seed([7; 32]);
+    let mut b = ChaCha20Rng::from_seed([7; 32]);
+    let result = (0..100).all(|_| a.next_u64() == b.next_u64());
+    if result {
+        println!("ChaCha20Rng::from_seed reproducible passed");
+    } else {
+        println!("ChaCha20Rng::from_seed reproducible error: streams differ");
+    }
+
+    match (ChaCha20Rng::new(), ChaCha20Rng::new()) {
+        (Ok(mut a), Ok(mut b)) => {
+            let result = a.next_u64() != b.next_u64();
+            if result {
+                println!("ChaCha20Rng::new independent passed");
+            } else {
+                println!("ChaCha20Rng::new independent error: same stream twice");
+            }
+        }
+        (Err(e), _) | (_, Err(e)) => println!("ChaCha20Rng::new error: {e}"),
+    }
+}
+
+/* Every value of [0, n[ must appear about draws / n times. */
+pub fn test_uniform() {
+    let mut rng = ChaCha20Rng::from_seed([1; 32]);
+    let draws = 7000;
+    for n in [1, 7, 8, 9] {
+        let mut counts = vec![0; n as usize];
+        for _ in 0..draws {
+            counts[uniform_i64(n, &mut rng) as usize] += 1;
+        }
+        let expected = draws / n;
+        let result = counts
+            .iter()
+            .all(|&c| (c - expected).abs() < expected / 5 + 1);
+        if result {
+            println!("uniform_i64({n})={counts:?} passed");
+        } else {
+            println!("uniform_i64({n})={counts:?} error: expected about {expected} each");
+        }
+    }
+
+    let big = Scalable::pow2(130) + 5;
+    let result = (0..200).all(|_| uniform_scalable(&big, &mut rng) < big);
+    let top = (0..200).any(|_| uniform_scalable(&big, &mut rng).bits() == 130);
+    if result && top {
+        println!("uniform_scalable(2^130+5) passed");
+    } else {
+        println!("uniform_scalable(2^130+5) error: in range {result}, reaches top {top}");
+    }
+
+    let n = Scalable::from(9i64);
+    let mut counts = [0; 9];
+    for _ in 0..draws {
+        counts[uniform_scalable(&n, &mut rng).to_i64().unwrap() as usize] += 1;
+    }
+    let expected = draws / 9;
+    let result = counts.iter().all(|&c| (c - expected).abs() < expected / 5);
+    if result {
+        println!("uniform_scalable(9)={counts:?} passed");
+    } else {
+        println!("uniform_scalable(9)={counts:?} error: expected about {expected} each");
+    }
+}
+
+pub fn test_rngs() {
+    test_chacha20_block();
+    println!();
+    test_chacha20_rng();
+    println!();
+    test_uniform();
+    println!();
+}
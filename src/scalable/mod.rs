@@ -1,5 +1,6 @@
 pub mod scalable_basic_arithmetics;
 pub mod scalable_break_ciphers;
+pub mod scalable_certificates;
 pub mod scalable_ciphers;
 pub mod scalable_encoding_msg;
 pub mod scalable_generate_primes;
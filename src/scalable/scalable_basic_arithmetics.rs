@@ -1 +1,190 @@
+use super::scalables::{Scalable, div, modulo};
 
+/* Greater common (positive) divisor of two integers, not both zero.
+ * @param a integer
+ * @param b integer
+ */
+pub fn gcd(a: &Scalable, b: &Scalable) -> Scalable {
+    let mut a = a.abs();
+    let mut b = b.abs();
+
+    while !b.is_zero() {
+        let (_, r) = div(&a, &b);
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/* Extended euclidean division of two integers NOT RUST DEFAULT
+ * Given non-zero entries a b computes triple (u, v, d) such that
+ * a*u + b*v = d and d is gcd of a and b.
+ * @param a non-zero integer
+ * @param b non-zero integer.
+ */
+pub fn bezout(a: &Scalable, b: &Scalable) -> (Scalable, Scalable, Scalable) {
+    let mut a = a.clone();
+    let mut b = b.clone();
+    let (mut u1, mut v1, mut u2, mut v2) = (
+        Scalable::one(),
+        Scalable::zero(),
+        Scalable::zero(),
+        Scalable::one(),
+    );
+
+    loop {
+        let (q, r) = div(&a, &b);
+        if r.is_zero() {
+            return (u2, v2, b);
+        } else {
+            a = b;
+            b = r;
+            (u1, v1, u2, v2) = (u2.clone(), v2.clone(), u1 - &q * &u2, v1 - &q * &v2);
+        }
+    }
+}
+
+/* Jacobi symbol (a/n), equal to the Legendre symbol when n is prime.
+ * Computed by quadratic reciprocity without factoring n.
+ * @param a integer
+ * @param n odd positive integer.
+ */
+pub fn jacobi(a: &Scalable, n: &Scalable) -> i64 {
+    if n.is_negative() || n.is_even() {
+        panic!("n must be odd and > 0");
+    }
+    let mut a = modulo(a, n);
+    let mut n = n.clone();
+    let mut result = 1;
+    while !a.is_zero() {
+        let twos = a.trailing_zeros();
+        a = a >> twos;
+        let n_mod_8 = n.rem_u64(8);
+        if twos % 2 == 1 && (n_mod_8 == 3 || n_mod_8 == 5) {
+            result = -result;
+        }
+        if a.rem_u64(4) == 3 && n_mod_8 % 4 == 3 {
+            result = -result;
+        }
+        let r = modulo(&n, &a);
+        n = a;
+        a = r;
+    }
+    if n.is_one() { result } else { 0 }
+}
+
+// ========================= TESTING =========================
+
+fn s(x: &str) -> Scalable {
+    Scalable::from_str_radix(x, 10).unwrap()
+}
+
+pub fn test_gcd() {
+    let cases = vec![
+        (("32", "6"), "2"),
+        (("18", "12"), "6"),
+        (("-18", "-12"), "6"),
+        (("7", "3"), "1"),
+        (
+            ("1208925819614629174706176", "1180591620717411303424"),
+            "1180591620717411303424",
+        ),
+        (
+            (
+                "170141183460469231731687303715884105727",
+                "618970019642690137449562111",
+            ),
+            "1",
+        ),
+    ];
+
+    for ele in cases {
+        let result = gcd(&s(ele.0.0), &s(ele.0.1));
+        if result == s(ele.1) {
+            println!("gcd({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "gcd({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_bezout() {
+    let cases = vec![
+        (("18", "22"), ("5", "-4", "2")),
+        (("22", "18"), ("-4", "5", "2")),
+        (("17", "21"), ("5", "-4", "1")),
+        (("21", "17"), ("-4", "5", "1")),
+    ];
+
+    for ele in cases {
+        let result = bezout(&s(ele.0.0), &s(ele.0.1));
+        if result == (s(ele.1.0), s(ele.1.1), s(ele.1.2)) {
+            println!(
+                "bezout({},{})=({},{},{}) passed",
+                ele.0.0, ele.0.1, result.0, result.1, result.2
+            );
+        } else {
+            println!(
+                "bezout({},{})=({},{},{}) error: expected {:?}",
+                ele.0.0, ele.0.1, result.0, result.1, result.2, ele.1
+            );
+        }
+    }
+
+    let (a, b) = (
+        s("170141183460469231731687303715884105727"),
+        s("618970019642690137449562111"),
+    );
+    let (u, v, d) = bezout(&a, &b);
+    if &a * &u + &b * &v == d && d.is_one() {
+        println!("bezout({},{}) identity passed", a, b);
+    } else {
+        println!(
+            "bezout({},{})=({},{},{}) error: identity broken",
+            a, b, u, v, d
+        );
+    }
+}
+
+pub fn test_jacobi() {
+    let cases = vec![
+        (("1", "1"), 1),
+        (("3", "7"), -1),
+        (("6", "21"), 0),
+        (("-1", "11"), -1),
+        (("1001", "9907"), -1),
+        (("2", "170141183460469231731687303715884105727"), 1),
+        (("3", "170141183460469231731687303715884105727"), -1),
+        (
+            (
+                "618970019642690137449562111",
+                "170141183460469231731687303715884105727",
+            ),
+            1,
+        ),
+    ];
+
+    for ele in cases {
+        let result = jacobi(&s(ele.0.0), &s(ele.0.1));
+        if result == ele.1 {
+            println!("jacobi({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "jacobi({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_basic_arithmetics() {
+    test_gcd();
+    println!();
+    test_bezout();
+    println!();
+    test_jacobi();
+    println!();
+}
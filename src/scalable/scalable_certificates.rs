@@ -0,0 +1,592 @@
+/********** Primality certificates **********/
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::builtin::generate_primes::eratosthenes;
+use crate::rng::{Rng, SplitMix64, uniform_scalable};
+
+use super::{
+    scalable_basic_arithmetics::gcd,
+    scalable_power::mod_power,
+    scalable_test_primes::baillie_psw,
+    scalables::{Scalable, modulo},
+};
+
+/* Bound on primes divided out of n - 1 when certifying. */
+const TRIAL_DIVISION_LIMIT: i64 = 1 << 16;
+
+/* Size in bits below which certificates are Pratt ones. */
+pub const PRATT_BITS: usize = 32;
+
+/* Bound on the number of certificates, counted with repetitions, in a
+   certificate parsed from text. A factor shared by several lines is
+   copied under each of them, so chains of shared factors would
+   otherwise grow exponentially with the text length.
+*/
+pub const MAX_CERTIFICATE_NODES: usize = 1 << 16;
+
+/* Prime factor q^exponent of n - 1 together with the certificate of q. */
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimeFactor {
+    pub q: Scalable,
+    pub exponent: u32,
+    pub certificate: Certificate,
+}
+
+/* Proof that an integer n is prime, checked by `verify_certificate`
+   without trusting whoever produced it.
+
+   Pratt: n - 1 is the product of the listed factors and the witness a
+   has order n - 1 modulo n, i.e. a^(n - 1) = 1 and a^((n - 1) / q) != 1
+   for every prime q dividing n - 1.
+
+   Pocklington: the listed factors multiply to some F dividing n - 1
+   with F^2 > n, a^(n - 1) = 1 and gcd(a^((n - 1) / q) - 1, n) = 1 for
+   every listed q. Then every prime divisor of n is greater than
+   sqrt(n), so n is prime.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Certificate {
+    Pratt {
+        n: Scalable,
+        witness: Scalable,
+        factors: Vec<PrimeFactor>,
+    },
+    Pocklington {
+        n: Scalable,
+        witness: Scalable,
+        factors: Vec<PrimeFactor>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CertificateError {
+    Malformed,
+    MissingFactor,
+    TooLarge,
+}
+
+impl fmt::Display for CertificateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CertificateError::Malformed => write!(f, "malformed certificate"),
+            CertificateError::MissingFactor => write!(f, "factor without certificate"),
+            CertificateError::TooLarge => write!(f, "certificate too large"),
+        }
+    }
+}
+
+impl std::error::Error for CertificateError {}
+
+impl Certificate {
+    /* Integer proven prime. */
+    pub fn n(&self) -> &Scalable {
+        match self {
+            Certificate::Pratt { n, .. } | Certificate::Pocklington { n, .. } => n,
+        }
+    }
+
+    pub fn witness(&self) -> &Scalable {
+        match self {
+            Certificate::Pratt { witness, .. } | Certificate::Pocklington { witness, .. } => {
+                witness
+            }
+        }
+    }
+
+    pub fn factors(&self) -> &[PrimeFactor] {
+        match self {
+            Certificate::Pratt { factors, .. } | Certificate::Pocklington { factors, .. } => {
+                factors
+            }
+        }
+    }
+
+    /* Lines of the textual form, each prime only once, depth first. */
+    fn write_lines(&self, seen: &mut Vec<Scalable>, lines: &mut Vec<String>) {
+        if seen.contains(self.n()) {
+            return;
+        }
+        seen.push(self.n().clone());
+        let kind = match self {
+            Certificate::Pratt { .. } => "pratt",
+            Certificate::Pocklington { .. } => "pocklington",
+        };
+        let mut line = format!("{kind} {} {}", self.n(), self.witness());
+        for f in self.factors() {
+            line += &format!(" {}^{}", f.q, f.exponent);
+        }
+        lines.push(line);
+        for f in self.factors() {
+            f.certificate.write_lines(seen, lines);
+        }
+    }
+}
+
+/* Textual form: one line per prime of the certificate, the proven prime
+   first, each line reading
+   `pratt|pocklington <n> <witness> <q1>^<e1> <q2>^<e2> ...`
+   in decimal. Every listed factor has its own line.
+*/
+impl fmt::Display for Certificate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut lines = vec![];
+        self.write_lines(&mut vec![], &mut lines);
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+type Line = (bool, Scalable, Vec<(Scalable, u32)>);
+
+/* Rebuild the certificate of n from parsed lines. Factors being smaller
+   than n, recursion always ends. Each prime is built once and kept in
+   built together with its number of nodes, which are summed up to
+   MAX_CERTIFICATE_NODES.
+*/
+fn build(
+    n: &Scalable,
+    lines: &HashMap<Scalable, Line>,
+    built: &mut HashMap<Scalable, (Certificate, usize)>,
+) -> Result<(Certificate, usize), CertificateError> {
+    if let Some(done) = built.get(n) {
+        return Ok(done.clone());
+    }
+    let (pratt, witness, factors) = lines.get(n).ok_or(CertificateError::MissingFactor)?;
+    let mut result = vec![];
+    let mut nodes = 1;
+    for (q, exponent) in factors {
+        let (certificate, size) = build(q, lines, built)?;
+        nodes += size;
+        if nodes > MAX_CERTIFICATE_NODES {
+            return Err(CertificateError::TooLarge);
+        }
+        result.push(PrimeFactor {
+            q: q.clone(),
+            exponent: *exponent,
+            certificate,
+        });
+    }
+    let (n, witness) = (n.clone(), witness.clone());
+    let certificate = if *pratt {
+        Certificate::Pratt {
+            n: n.clone(),
+            witness,
+            factors: result,
+        }
+    } else {
+        Certificate::Pocklington {
+            n: n.clone(),
+            witness,
+            factors: result,
+        }
+    };
+    built.insert(n, (certificate.clone(), nodes));
+    Ok((certificate, nodes))
+}
+
+impl FromStr for Certificate {
+    type Err = CertificateError;
+
+    fn from_str(s: &str) -> Result<Certificate, CertificateError> {
+        let parse = |x: &str| Scalable::from_str_radix(x, 10).ok_or(CertificateError::Malformed);
+        let mut lines = HashMap::new();
+        let mut first = None;
+        for line in s.lines().filter(|l| !l.trim().is_empty()) {
+            let mut words = line.split_whitespace();
+            let pratt = match words.next() {
+                Some("pratt") => true,
+                Some("pocklington") => false,
+                _ => return Err(CertificateError::Malformed),
+            };
+            let n = parse(words.next().ok_or(CertificateError::Malformed)?)?;
+            let witness = parse(words.next().ok_or(CertificateError::Malformed)?)?;
+            let mut factors = vec![];
+            for word in words {
+                let (q, e) = word.split_once('^').ok_or(CertificateError::Malformed)?;
+                let q = parse(q)?;
+                let e = e.parse().map_err(|_| CertificateError::Malformed)?;
+                if q >= n || q.bits() < 2 || factors.iter().any(|(p, _)| *p == q) {
+                    return Err(CertificateError::Malformed);
+                }
+                factors.push((q, e));
+            }
+            if first.is_none() {
+                first = Some(n.clone());
+            }
+            if lines.insert(n, (pratt, witness, factors)).is_some() {
+                return Err(CertificateError::Malformed);
+            }
+        }
+        let n = first.ok_or(CertificateError::Malformed)?;
+        Ok(build(&n, &lines, &mut HashMap::new())?.0)
+    }
+}
+
+/* Check a primality certificate and, recursively, those of the factors
+   it relies on. True only if the certificate proves its integer prime.
+   Exponents come from untrusted text, so a factor is rejected before
+   being multiplied out as soon as its power cannot divide n - 1.
+   A prime appearing under several factors is checked only once.
+   @param certificate certificate to check.
+*/
+pub fn verify_certificate(certificate: &Certificate) -> bool {
+    verify_once(certificate, &mut HashSet::new())
+}
+
+/* `verify_certificate` skipping the primes already in verified, and
+   adding to it those it proves.
+*/
+fn verify_once(certificate: &Certificate, verified: &mut HashSet<Scalable>) -> bool {
+    let (n, a) = (certificate.n(), certificate.witness());
+    if verified.contains(n) {
+        return true;
+    }
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    let n_minus_one = n - 1;
+    let mut f = Scalable::one();
+    for factor in certificate.factors() {
+        if factor.exponent as usize > n.bits()
+            || factor.q != *factor.certificate.n()
+            || !verify_once(&factor.certificate, verified)
+        {
+            return false;
+        }
+        for _ in 0..factor.exponent {
+            f = &f * &factor.q;
+            if f > n_minus_one {
+                return false;
+            }
+        }
+    }
+    let covered = match certificate {
+        Certificate::Pratt { .. } => f == n_minus_one,
+        Certificate::Pocklington { .. } => modulo(&n_minus_one, &f).is_zero() && &f * &f > *n,
+    };
+    if !covered || !mod_power(a, &n_minus_one, n).is_one() {
+        return false;
+    }
+    let proven = certificate.factors().iter().all(|factor| {
+        let x = mod_power(a, &(&n_minus_one / &factor.q), n);
+        match certificate {
+            Certificate::Pratt { .. } => !x.is_one(),
+            Certificate::Pocklington { .. } => gcd(&(x - 1), n).is_one(),
+        }
+    });
+    if proven {
+        verified.insert(n.clone());
+    }
+    proven
+}
+
+/* Pratt certificate of a prime below 2^32, n - 1 being factored by
+   trial division and the witness being the least primitive root.
+   None when p is not such a prime.
+   @param p prime to certify.
+*/
+pub fn pratt_certificate(p: &Scalable) -> Option<Certificate> {
+    if p.bits() > PRATT_BITS || !baillie_psw(p) {
+        return None;
+    }
+    let mut m = p.to_u64()? - 1;
+    let mut factors = vec![];
+    let mut q = 2;
+    while q * q <= m {
+        let mut exponent = 0;
+        while m % q == 0 {
+            m /= q;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            factors.push((q, exponent));
+        }
+        q += 1;
+    }
+    if m > 1 {
+        factors.push((m, 1));
+    }
+
+    let n_minus_one = p - 1;
+    let witness = (2..)
+        .map(|a: u64| Scalable::from(a))
+        .take_while(|a| a < p)
+        .find(|a| {
+            factors
+                .iter()
+                .all(|(q, _)| !mod_power(a, &(&n_minus_one / *q as i64), p).is_one())
+        })
+        .unwrap_or(Scalable::one());
+    let factors = factors
+        .into_iter()
+        .map(|(q, exponent)| {
+            let q = Scalable::from(q);
+            pratt_certificate(&q).map(|certificate| PrimeFactor {
+                q,
+                exponent,
+                certificate,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some(Certificate::Pratt {
+        n: p.clone(),
+        witness,
+        factors,
+    })
+}
+
+/* Pocklington certificate of n given already certified prime factors
+   of n - 1 whose product F satisfies F^2 > n. The witness is drawn at
+   random, None when none was found after a few tries, which happens
+   with negligible probability when n is prime.
+   @param n prime to certify.
+   @param factors certified prime factors of n - 1.
+   @param rng random generator drawing witnesses.
+*/
+pub fn pocklington_certificate(
+    n: &Scalable,
+    factors: Vec<PrimeFactor>,
+    rng: &mut impl Rng,
+) -> Option<Certificate> {
+    let n_minus_one = n - 1;
+    for _ in 0..64 {
+        let witness = uniform_scalable(&(n - 3), rng) + 2;
+        let certificate = Certificate::Pocklington {
+            n: n.clone(),
+            witness,
+            factors: factors.clone(),
+        };
+        if !mod_power(certificate.witness(), &n_minus_one, n).is_one() {
+            return None;
+        }
+        if verify_certificate(&certificate) {
+            return Some(certificate);
+        }
+    }
+    None
+}
+
+/* Certificate of a prime n, when n - 1 factors far enough: primes up to
+   2^16 are divided out of n - 1 and a cofactor passing Baillie-PSW is
+   certified recursively. Pratt certificate below 2^32, Pocklington one
+   above. None when n is not prime or n - 1 does not factor enough.
+   @param n prime to certify.
+   @param rng random generator drawing witnesses.
+*/
+pub fn certify(n: &Scalable, rng: &mut impl Rng) -> Option<Certificate> {
+    if n.bits() <= PRATT_BITS {
+        return pratt_certificate(n);
+    }
+    if !baillie_psw(n) {
+        return None;
+    }
+    let mut m = n - 1;
+    let mut factors = vec![];
+    for q in eratosthenes(TRIAL_DIVISION_LIMIT) {
+        let mut exponent = 0;
+        while m.rem_u64(q as u64) == 0 {
+            m = &m / q;
+            exponent += 1;
+        }
+        if exponent > 0 {
+            let q = Scalable::from(q);
+            let certificate = pratt_certificate(&q)?;
+            factors.push(PrimeFactor {
+                q,
+                exponent,
+                certificate,
+            });
+        }
+    }
+    if !m.is_one() && baillie_psw(&m) {
+        let certificate = certify(&m, rng)?;
+        factors.push(PrimeFactor {
+            q: m,
+            exponent: 1,
+            certificate,
+        });
+    }
+    pocklington_certificate(n, factors, rng)
+}
+
+// ========================= TESTING =========================
+
+fn s(x: &str) -> Scalable {
+    Scalable::from_str_radix(x, 10).unwrap()
+}
+
+pub fn test_pratt_certificate() {
+    let cases = vec![
+        ("2", true),
+        ("3", true),
+        ("7", true),
+        ("65537", true),
+        ("4294967291", true),
+        ("1", false),
+        ("561", false),
+        ("4294967297", false),
+        ("4294967311", false),
+    ];
+
+    for ele in cases {
+        let result = pratt_certificate(&s(ele.0));
+        let valid = result.as_ref().is_some_and(verify_certificate);
+        if result.is_some() == ele.1 && valid == ele.1 {
+            println!("pratt_certificate({})={} passed", ele.0, valid);
+        } else {
+            println!(
+                "pratt_certificate({})={:?} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+/* First case is built from 4294967291 by repeatedly taking the first
+   prime 2kp + 1 with k >= 40000, so n - 1 factors all the way down.
+   2^127 - 1 is prime but its n - 1 keeps a 72 bits composite cofactor.
+*/
+pub fn test_certify() {
+    let mut rng = SplitMix64::new(39);
+    let cases = vec![
+        ("7239872279117237170045288004341793756247010035733", true),
+        ("4294967311", true),
+        ("170141183460469231731687303715884105727", false),
+        ("340282366920938463463374607431768211507", false),
+        ("170141183460469231731687303715884105729", false),
+        ("1000000000000000000000000000057", false),
+        (
+            "6864797660130609714981900799081393217269435300143305409394463459\
+             18554318339765605212255964066145455497729631139148085803712198799\
+             971664381257402829111505715",
+            false,
+        ),
+    ];
+
+    for ele in cases {
+        let result = certify(&s(ele.0), &mut rng);
+        let valid = result.as_ref().is_some_and(verify_certificate);
+        if valid == ele.1 {
+            println!("certify({})={} passed", ele.0, valid);
+        } else {
+            println!("certify({})={:?} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_verify_certificate() {
+    let mut rng = SplitMix64::new(39);
+    let good = certify(
+        &s("7239872279117237170045288004341793756247010035733"),
+        &mut rng,
+    )
+    .unwrap();
+    let text = good.to_string();
+
+    let mut wrong_witness = good.clone();
+    if let Certificate::Pocklington { witness, .. } = &mut wrong_witness {
+        *witness = Scalable::one();
+    }
+    let mut too_small = good.clone();
+    if let Certificate::Pocklington { factors, .. } = &mut too_small {
+        factors.pop();
+    }
+    let fake_pratt =
+        "pratt 561 2 2^4 5^1 7^1\npratt 2 1\npratt 5 2 2^2\npratt 7 3 2^1 3^1\npratt 3 2 2^1";
+    let lying_factor = "pratt 7 3 2^1 3^1\npratt 2 1\npratt 3 1 2^1";
+
+    let cases = vec![
+        ("certify(7239...5733)", Ok(good.clone()), true),
+        (
+            "parsed certify(7239...5733)",
+            text.parse::<Certificate>(),
+            true,
+        ),
+        ("wrong witness", Ok(wrong_witness), false),
+        ("factored part too small", Ok(too_small), false),
+        ("pratt 561", fake_pratt.parse(), false),
+        ("pratt 7, bad witness for 3", lying_factor.parse(), false),
+        (
+            "pratt 7, missing 3",
+            "pratt 7 3 2^1 3^1\npratt 2 1".parse(),
+            false,
+        ),
+        ("pratt 7, cyclic", "pratt 7 3 7^1".parse(), false),
+        (
+            "pratt 7, power of 2 above n - 1",
+            "pratt 7 3 2^3 3^1\npratt 2 1\npratt 3 2 2^1".parse(),
+            false,
+        ),
+        (
+            "pratt 7, repeated factor",
+            "pratt 7 3 2^1 3^1 3^0\npratt 2 1\npratt 3 2 2^1".parse(),
+            false,
+        ),
+        ("garbage", "prime 7".parse(), false),
+    ];
+
+    for ele in cases {
+        let (name, certificate, exp) = ele;
+        let result = certificate.as_ref().is_ok_and(verify_certificate);
+        if result == exp {
+            println!("verify_certificate({name})={result} passed");
+        } else {
+            println!("verify_certificate({name})={result} error: expected {exp}");
+        }
+    }
+
+    let huge = "pratt 7 3 2^4000000000 3^1\npratt 2 1\npratt 3 2 2^1";
+    let start = Instant::now();
+    let result = huge
+        .parse::<Certificate>()
+        .as_ref()
+        .is_ok_and(verify_certificate);
+    let elapsed = start.elapsed();
+    if !result && elapsed < Duration::from_secs(1) {
+        println!("verify_certificate(pratt 7, 2^4000000000)={result} passed");
+    } else {
+        println!(
+            "verify_certificate(pratt 7, 2^4000000000)={result} in {elapsed:?} error: \
+             expected false at once"
+        );
+    }
+
+    /* Line i lists the primes of lines i + 1 and i + 2, so the tree
+       written out has Fibonacci many nodes.
+    */
+    let shared = (0..100)
+        .map(|i| format!("pratt {} 2 {}^1 {}^1", 1000 - i, 999 - i, 998 - i))
+        .chain(["pratt 900 2".to_string(), "pratt 899 2".to_string()])
+        .collect::<Vec<_>>()
+        .join("\n");
+    let start = Instant::now();
+    let result = shared.parse::<Certificate>();
+    let elapsed = start.elapsed();
+    if result == Err(CertificateError::TooLarge) && elapsed < Duration::from_secs(1) {
+        println!("Certificate::from_str(100 shared lines) passed");
+    } else {
+        println!(
+            "Certificate::from_str(100 shared lines) in {elapsed:?} error: expected TooLarge \
+             at once"
+        );
+    }
+
+    let result = text.parse::<Certificate>();
+    if result.as_ref() == Ok(&good) {
+        println!("Certificate round trip through text passed");
+    } else {
+        println!("Certificate round trip through text error: got {result:?}");
+    }
+}
+
+pub fn test_certificates() {
+    test_pratt_certificate();
+    println!();
+    test_certify();
+    println!();
+    test_verify_certificate();
+    println!();
+}
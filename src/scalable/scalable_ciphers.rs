@@ -1 +1,1162 @@
+/********** RSA Cipher **********/
 
+use std::fmt;
+
+use crate::builtin::{chinese_remainder::garner, generate_primes::eratosthenes};
+use crate::hash::{HashFunction, sha256::sha256};
+use crate::keys::{RsaKeyPair, RsaPrivateKey, RsaPublicKey};
+use crate::rng::{Rng, SplitMix64, random_bits};
+
+use super::{
+    scalable_basic_arithmetics::gcd,
+    scalable_generate_primes::strong_prime,
+    scalable_power::mod_power,
+    scalable_test_primes::baillie_psw,
+    scalables::{Scalable, modulo},
+};
+
+/* Public exponent used for generated keys. */
+pub const RSA_PUBLIC_EXPONENT: u64 = 65537;
+
+/* Trial division by small primes followed by Baillie-PSW test. */
+fn is_probable_prime(p: &Scalable, small_primes: &[i64]) -> bool {
+    for q in small_primes {
+        if p.rem_u64(*q as u64) == 0 {
+            return *p == *q;
+        }
+    }
+    baillie_psw(p)
+}
+
+/* Random prime of exactly given bit size with its two most significant
+   bits set, such that p - 1 is coprime to e.
+*/
+fn random_rsa_prime(
+    bits: usize,
+    e: &Scalable,
+    small_primes: &[i64],
+    rng: &mut impl Rng,
+) -> Scalable {
+    loop {
+        let mut p = random_bits(bits, rng);
+        p.set_bit(bits - 1);
+        p.set_bit(bits - 2);
+        p.set_bit(0);
+        if gcd(e, &(&p - 1)).is_one() && is_probable_prime(&p, small_primes) {
+            return p;
+        }
+    }
+}
+
+/* How primes of a generated RSA key are drawn. */
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PrimeKind {
+    /* Uniformly random primes. */
+    Random,
+    /* Strong primes from Gordon's algorithm, see
+    `scalable_generate_primes::strong_prime`, for standards asking p - 1
+    and p + 1 to have large prime factors. */
+    Strong,
+}
+
+/* Strong prime of exactly given bit size, with its two most significant
+   bits set, such that p - 1 is coprime to e.
+*/
+fn strong_rsa_prime(bits: usize, e: &Scalable, rng: &mut impl Rng) -> Scalable {
+    loop {
+        let p = strong_prime(bits, rng).p;
+        if gcd(e, &(&p - 1)).is_one() {
+            return p;
+        }
+    }
+}
+
+impl RsaKeyPair<Scalable> {
+    /* Generate an RSA key pair whose modulus has exactly given bit size.
+       Both primes have their two top bits set so that their product
+       has the requested size, are far enough from each other for Fermat
+       factorization to be useless, and are such that public exponent
+       65537 is invertible modulo p - 1 and q - 1.
+       @param bits size of modulus n, at least 32.
+       @param rng random generator.
+    */
+    pub fn generate(bits: usize, rng: &mut impl Rng) -> RsaKeyPair<Scalable> {
+        RsaKeyPair::generate_multi_prime(bits, 2, rng)
+    }
+
+    /* Generate a multi-prime RSA key pair whose modulus has exactly given
+       bit size. Primes are drawn as for `generate`, pairwise far from each
+       other, and the whole draw is retried when their product falls one
+       bit short.
+       @param bits size of modulus n, at least 32.
+       @param k number of primes, at least 2, each one having at least 16
+       bits.
+       @param rng random generator.
+    */
+    pub fn generate_multi_prime(bits: usize, k: usize, rng: &mut impl Rng) -> RsaKeyPair<Scalable> {
+        RsaKeyPair::generate_with(bits, k, PrimeKind::Random, rng)
+    }
+
+    /* Generate a multi-prime RSA key pair as `generate_multi_prime`,
+       primes being drawn according to kind.
+       @param bits size of modulus n, at least 32.
+       @param k number of primes, at least 2, each one having at least 16
+       bits, or 64 bits for strong primes.
+       @param kind how primes are drawn.
+       @param rng random generator.
+    */
+    pub fn generate_with(
+        bits: usize,
+        k: usize,
+        kind: PrimeKind,
+        rng: &mut impl Rng,
+    ) -> RsaKeyPair<Scalable> {
+        if bits < 32 {
+            panic!("bits must be >= 32");
+        }
+        if k < 2 || bits / k < 16 {
+            panic!("k must be >= 2 and bits / k >= 16");
+        }
+        if kind == PrimeKind::Strong && bits / k < 64 {
+            panic!("bits / k must be >= 64 for strong primes");
+        }
+        let e = Scalable::from(RSA_PUBLIC_EXPONENT);
+        let small_primes = eratosthenes(1000);
+
+        loop {
+            let mut primes: Vec<Scalable> = vec![];
+            for i in 0..k {
+                let size = bits / k + if i < bits % k { 1 } else { 0 };
+                let min_distance = Scalable::pow2(if size > 200 { size - 100 } else { size / 2 });
+                let r = loop {
+                    let r = match kind {
+                        PrimeKind::Random => random_rsa_prime(size, &e, &small_primes, rng),
+                        PrimeKind::Strong => strong_rsa_prime(size, &e, rng),
+                    };
+                    if primes.iter().all(|x| (x - &r).abs() > min_distance) {
+                        break r;
+                    }
+                };
+                primes.push(r);
+            }
+            let n = primes.iter().fold(Scalable::one(), |acc, r| acc * r);
+            if n.bits() == bits {
+                let private =
+                    RsaPrivateKey::from_primes(primes, e).expect("generated key is valid");
+                return RsaKeyPair::from_private(private);
+            }
+        }
+    }
+}
+
+/* Encryption using RSA cryptosystem.
+   @param m integer hash of message, in [0, n[.
+   @param pub_key public key of RSA cryptosystem.
+*/
+pub fn encrypt_rsa(m: &Scalable, pub_key: &RsaPublicKey<Scalable>) -> Scalable {
+    mod_power(m, pub_key.e(), pub_key.n())
+}
+
+/* Decryption using RSA cryptosystem.
+   Works modulo each prime and recombines with chinese remainder theorem
+   when primes of the key are known.
+   @param m integer hash of encrypted message.
+   @param priv_key private key of RSA cryptosystem.
+*/
+pub fn decrypt_rsa(m: &Scalable, priv_key: &RsaPrivateKey<Scalable>) -> Scalable {
+    let primes = priv_key.primes();
+    if primes.is_empty() {
+        return mod_power(m, priv_key.d(), priv_key.n());
+    }
+    let residues: Vec<Scalable> = primes
+        .iter()
+        .zip(priv_key.exponents())
+        .map(|(r, d)| mod_power(m, d, r))
+        .collect();
+    garner(&residues, primes, priv_key.coefficients())
+}
+
+/********** RSA Signature **********/
+
+/* Message representative of a byte message: its SHA-256 digest read as
+   a big endian integer, reduced modulo n for keys smaller than 256 bits.
+*/
+fn message_representative(msg: &[u8], n: &Scalable) -> Scalable {
+    modulo(&Scalable::from_bytes_be(&sha256(msg)), n)
+}
+
+/* RSA signature of a message representative, m^d mod n.
+   @param m message representative, in [0, n[.
+   @param priv_key private key of RSA cryptosystem.
+*/
+pub fn sign_rsa(m: &Scalable, priv_key: &RsaPrivateKey<Scalable>) -> Scalable {
+    decrypt_rsa(m, priv_key)
+}
+
+/* Check an RSA signature, s^e mod n must give back the message
+   representative.
+   @param m message representative, in [0, n[.
+   @param s signature
+   @param pub_key public key of RSA cryptosystem.
+*/
+pub fn verify_rsa(m: &Scalable, s: &Scalable, pub_key: &RsaPublicKey<Scalable>) -> bool {
+    !s.is_negative() && s < pub_key.n() && encrypt_rsa(s, pub_key) == *m
+}
+
+/* Hash-then-sign a byte message with RSA.
+   @param msg message to sign.
+   @param priv_key private key of RSA cryptosystem.
+*/
+pub fn sign_rsa_message(msg: &[u8], priv_key: &RsaPrivateKey<Scalable>) -> Scalable {
+    sign_rsa(&message_representative(msg, priv_key.n()), priv_key)
+}
+
+/* Check an RSA signature of a byte message produced by
+   `sign_rsa_message`.
+   @param msg signed message.
+   @param s signature
+   @param pub_key public key of RSA cryptosystem.
+*/
+pub fn verify_rsa_message(msg: &[u8], s: &Scalable, pub_key: &RsaPublicKey<Scalable>) -> bool {
+    verify_rsa(&message_representative(msg, pub_key.n()), s, pub_key)
+}
+
+/********** PKCS#1 v1.5 encryption **********/
+
+/* Failure of a padded RSA operation. Every decryption failure, bad
+   padding included, is reported as the same `Decryption` variant so that
+   callers cannot leak which check failed to an attacker.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RsaError {
+    MessageTooLong,
+    Decryption,
+    KeyTooSmall,
+}
+
+impl fmt::Display for RsaError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RsaError::MessageTooLong => write!(f, "message too long"),
+            RsaError::Decryption => write!(f, "decryption error"),
+            RsaError::KeyTooSmall => write!(f, "key too small"),
+        }
+    }
+}
+
+impl std::error::Error for RsaError {}
+
+/* PKCS#1 v1.5 encryption, RFC 8017 RSAES-PKCS1-v1_5. Message is padded
+   into EM = 0x00 || 0x02 || PS || 0x00 || M where PS is made of at least
+   8 random non-zero bytes, then encrypted.
+   @param msg message of at most k - 11 bytes, k being the size of n in
+   bytes.
+   @param pub_key public key of RSA cryptosystem.
+   @param rng random generator drawing padding.
+*/
+pub fn encrypt_pkcs1v15(
+    msg: &[u8],
+    pub_key: &RsaPublicKey<Scalable>,
+    rng: &mut impl Rng,
+) -> Result<Vec<u8>, RsaError> {
+    let k = pub_key.size_bytes();
+    if msg.len() + 11 > k {
+        return Err(RsaError::MessageTooLong);
+    }
+    let mut em = vec![0; k];
+    em[1] = 2;
+    for b in em[2..k - msg.len() - 1].iter_mut() {
+        while *b == 0 {
+            let mut byte = [0];
+            rng.fill_bytes(&mut byte);
+            *b = byte[0];
+        }
+    }
+    em[k - msg.len()..].copy_from_slice(msg);
+    let c = encrypt_rsa(&Scalable::from_bytes_be(&em), pub_key);
+    Ok(c.to_bytes_be_len(k).expect("ciphertext is smaller than n"))
+}
+
+/* PKCS#1 v1.5 decryption, RFC 8017 RSAES-PKCS1-v1_5. Padding is checked
+   scanning the whole encoded message without early exit, and any failure
+   gives back `RsaError::Decryption`.
+   @param ciphertext ciphertext of exactly k bytes, k being the size of n
+   in bytes.
+   @param priv_key private key of RSA cryptosystem.
+*/
+pub fn decrypt_pkcs1v15(
+    ciphertext: &[u8],
+    priv_key: &RsaPrivateKey<Scalable>,
+) -> Result<Vec<u8>, RsaError> {
+    let k = priv_key.size_bytes();
+    let c = Scalable::from_bytes_be(ciphertext);
+    if ciphertext.len() != k || k < 11 || c >= *priv_key.n() {
+        return Err(RsaError::Decryption);
+    }
+    let em = decrypt_rsa(&c, priv_key)
+        .to_bytes_be_len(k)
+        .expect("plaintext is smaller than n");
+
+    let mut bad = (em[0] != 0) as u8 | (em[1] != 2) as u8;
+    let mut found = 0u8;
+    let mut separator = 0;
+    for (i, b) in em.iter().enumerate().skip(2) {
+        let first_zero = (*b == 0) as u8 & !found & 1;
+        separator |= i * first_zero as usize;
+        found |= first_zero;
+    }
+    bad |= !found & 1;
+    bad |= (separator < 10) as u8;
+    if bad != 0 {
+        return Err(RsaError::Decryption);
+    }
+    Ok(em[separator + 1..].to_vec())
+}
+
+impl RsaPublicKey<Scalable> {
+    /* Encrypt a byte message, see `encrypt_pkcs1v15`. */
+    pub fn encrypt(&self, msg: &[u8], rng: &mut impl Rng) -> Result<Vec<u8>, RsaError> {
+        encrypt_pkcs1v15(msg, self, rng)
+    }
+}
+
+impl RsaPrivateKey<Scalable> {
+    /* Decrypt a byte message, see `decrypt_pkcs1v15`. */
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, RsaError> {
+        decrypt_pkcs1v15(ciphertext, self)
+    }
+}
+
+/********** RSA-OAEP encryption **********/
+
+/* MGF1 mask generation function, RFC 8017 appendix B.2.1.
+   @param seed seed from which mask is generated.
+   @param len length of the mask in bytes.
+   @param hash underlying hash function.
+*/
+pub fn mgf1(seed: &[u8], len: usize, hash: HashFunction) -> Vec<u8> {
+    let mut result = Vec::with_capacity(len + hash.output_len());
+    let mut counter: u32 = 0;
+    while result.len() < len {
+        let mut block = seed.to_vec();
+        block.extend_from_slice(&counter.to_be_bytes());
+        result.extend(hash.digest(&block));
+        counter += 1;
+    }
+    result.truncate(len);
+    result
+}
+
+fn xor_in_place(dest: &mut [u8], mask: &[u8]) {
+    for (d, m) in dest.iter_mut().zip(mask) {
+        *d ^= m;
+    }
+}
+
+/* OAEP encryption, RFC 8017 RSAES-OAEP. Message is padded into
+   EM = 0x00 || maskedSeed || maskedDB where DB = lHash || PS || 0x01 || M,
+   masks being derived with MGF1 from a random seed.
+   @param msg message of at most k - 2 * hLen - 2 bytes, k being the size
+   of n in bytes and hLen the size of a digest.
+   @param pub_key public key of RSA cryptosystem.
+   @param hash hash function used for label and MGF1.
+   @param label label associated with the message, often empty.
+   @param rng random generator drawing seed.
+*/
+pub fn encrypt_oaep(
+    msg: &[u8],
+    pub_key: &RsaPublicKey<Scalable>,
+    hash: HashFunction,
+    label: &[u8],
+    rng: &mut impl Rng,
+) -> Result<Vec<u8>, RsaError> {
+    let k = pub_key.size_bytes();
+    let h_len = hash.output_len();
+    if msg.len() + 2 * h_len + 2 > k {
+        return Err(RsaError::MessageTooLong);
+    }
+    let mut em = vec![0; k];
+    let (seed, db) = em[1..].split_at_mut(h_len);
+    db[..h_len].copy_from_slice(&hash.digest(label));
+    db[k - h_len - 2 - msg.len()] = 1;
+    db[k - h_len - 1 - msg.len()..].copy_from_slice(msg);
+    rng.fill_bytes(seed);
+    xor_in_place(db, &mgf1(seed, k - h_len - 1, hash));
+    xor_in_place(seed, &mgf1(db, h_len, hash));
+
+    let c = encrypt_rsa(&Scalable::from_bytes_be(&em), pub_key);
+    Ok(c.to_bytes_be_len(k).expect("ciphertext is smaller than n"))
+}
+
+/* OAEP decryption, RFC 8017 RSAES-OAEP. Every check is performed before
+   deciding, without early exit, and any failure gives back
+   `RsaError::Decryption`.
+   @param ciphertext ciphertext of exactly k bytes, k being the size of n
+   in bytes.
+   @param priv_key private key of RSA cryptosystem.
+   @param hash hash function used for label and MGF1.
+   @param label label the message was encrypted with.
+*/
+pub fn decrypt_oaep(
+    ciphertext: &[u8],
+    priv_key: &RsaPrivateKey<Scalable>,
+    hash: HashFunction,
+    label: &[u8],
+) -> Result<Vec<u8>, RsaError> {
+    let k = priv_key.size_bytes();
+    let h_len = hash.output_len();
+    let c = Scalable::from_bytes_be(ciphertext);
+    if ciphertext.len() != k || k < 2 * h_len + 2 || c >= *priv_key.n() {
+        return Err(RsaError::Decryption);
+    }
+    let mut em = decrypt_rsa(&c, priv_key)
+        .to_bytes_be_len(k)
+        .expect("plaintext is smaller than n");
+    let (y, rest) = em.split_at_mut(1);
+    let (seed, db) = rest.split_at_mut(h_len);
+    xor_in_place(seed, &mgf1(db, h_len, hash));
+    xor_in_place(db, &mgf1(seed, k - h_len - 1, hash));
+
+    let mut bad = y[0];
+    for (a, b) in db[..h_len].iter().zip(hash.digest(label)) {
+        bad |= a ^ b;
+    }
+    let mut found = 0u8;
+    let mut separator = 0;
+    for (i, b) in db.iter().enumerate().skip(h_len) {
+        let is_one = (*b == 1) as u8 & !found & 1;
+        bad |= (*b != 0) as u8 & (*b != 1) as u8 & !found & 1;
+        separator |= i * is_one as usize;
+        found |= is_one;
+    }
+    bad |= !found & 1;
+    if bad != 0 {
+        return Err(RsaError::Decryption);
+    }
+    Ok(db[separator + 1..].to_vec())
+}
+
+/********** RSA-PSS signature **********/
+
+/* EMSA-PSS encoding, RFC 8017 section 9.1.1. None when the encoded
+   message cannot hold a digest and the salt.
+*/
+fn emsa_pss_encode(
+    m_hash: &[u8],
+    em_bits: usize,
+    salt: &[u8],
+    hash: HashFunction,
+) -> Option<Vec<u8>> {
+    let h_len = hash.output_len();
+    let em_len = em_bits.div_ceil(8);
+    if em_len < h_len + salt.len() + 2 {
+        return None;
+    }
+    let mut prefixed = vec![0; 8];
+    prefixed.extend_from_slice(m_hash);
+    prefixed.extend_from_slice(salt);
+    let h = hash.digest(&prefixed);
+
+    let db_len = em_len - h_len - 1;
+    let mut em = vec![0; em_len];
+    em[db_len - salt.len() - 1] = 1;
+    em[db_len - salt.len()..db_len].copy_from_slice(salt);
+    xor_in_place(&mut em[..db_len], &mgf1(&h, db_len, hash));
+    em[0] &= 0xff >> (8 * em_len - em_bits);
+    em[db_len..em_len - 1].copy_from_slice(&h);
+    em[em_len - 1] = 0xbc;
+    Some(em)
+}
+
+/* EMSA-PSS verification, RFC 8017 section 9.1.2. */
+fn emsa_pss_verify(
+    m_hash: &[u8],
+    em: &[u8],
+    em_bits: usize,
+    salt_len: usize,
+    hash: HashFunction,
+) -> bool {
+    let h_len = hash.output_len();
+    let em_len = em_bits.div_ceil(8);
+    if em.len() != em_len || em_len < h_len + salt_len + 2 || em[em_len - 1] != 0xbc {
+        return false;
+    }
+    let db_len = em_len - h_len - 1;
+    let top_bits = 0xff >> (8 * em_len - em_bits);
+    if em[0] & !top_bits != 0 {
+        return false;
+    }
+    let h = &em[db_len..em_len - 1];
+    let mut db = em[..db_len].to_vec();
+    xor_in_place(&mut db, &mgf1(h, db_len, hash));
+    db[0] &= top_bits;
+    let ps_len = db_len - salt_len - 1;
+    if db[..ps_len].iter().any(|b| *b != 0) || db[ps_len] != 1 {
+        return false;
+    }
+
+    let mut prefixed = vec![0; 8];
+    prefixed.extend_from_slice(m_hash);
+    prefixed.extend_from_slice(&db[ps_len + 1..]);
+    hash.digest(&prefixed) == h
+}
+
+/* RSA-PSS signature, RFC 8017 RSASSA-PSS. Message digest is encoded
+   with a fresh random salt, so that signing twice gives two different
+   signatures.
+   @param msg message to sign.
+   @param priv_key private key of RSA cryptosystem.
+   @param hash hash function used for message digest and MGF1.
+   @param salt_len length of the salt in bytes, usually digest length.
+   @param rng random generator drawing salt.
+*/
+pub fn sign_pss(
+    msg: &[u8],
+    priv_key: &RsaPrivateKey<Scalable>,
+    hash: HashFunction,
+    salt_len: usize,
+    rng: &mut impl Rng,
+) -> Result<Vec<u8>, RsaError> {
+    let mut salt = vec![0; salt_len];
+    rng.fill_bytes(&mut salt);
+    let em = emsa_pss_encode(&hash.digest(msg), priv_key.size_bits() - 1, &salt, hash)
+        .ok_or(RsaError::KeyTooSmall)?;
+    let s = sign_rsa(&Scalable::from_bytes_be(&em), priv_key);
+    Ok(s.to_bytes_be_len(priv_key.size_bytes())
+        .expect("signature is smaller than n"))
+}
+
+/* Check an RSA-PSS signature, RFC 8017 RSASSA-PSS.
+   @param msg signed message.
+   @param signature signature of exactly k bytes, k being the size of n in
+   bytes.
+   @param pub_key public key of RSA cryptosystem.
+   @param hash hash function used for message digest and MGF1.
+   @param salt_len length of the salt used when signing.
+*/
+pub fn verify_pss(
+    msg: &[u8],
+    signature: &[u8],
+    pub_key: &RsaPublicKey<Scalable>,
+    hash: HashFunction,
+    salt_len: usize,
+) -> bool {
+    let s = Scalable::from_bytes_be(signature);
+    if signature.len() != pub_key.size_bytes() || s >= *pub_key.n() {
+        return false;
+    }
+    let em_bits = pub_key.size_bits() - 1;
+    match encrypt_rsa(&s, pub_key).to_bytes_be_len(em_bits.div_ceil(8)) {
+        Some(em) => emsa_pss_verify(&hash.digest(msg), &em, em_bits, salt_len, hash),
+        None => false,
+    }
+}
+
+// ========================= TESTING =========================
+
+/* Generator replaying given bytes, used to fix random values of known
+   answer tests.
+*/
+struct FixedBytes(Vec<u8>);
+
+impl Rng for FixedBytes {
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        let rest = self.0.split_off(dest.len());
+        dest.copy_from_slice(&self.0);
+        self.0 = rest;
+    }
+}
+
+fn hex_bytes(s: &str) -> Vec<u8> {
+    Scalable::from_hex(s)
+        .unwrap()
+        .to_bytes_be_len(s.len() / 2)
+        .unwrap()
+}
+
+/* 1024 bits key and ciphertexts produced by an independent implementation
+   (pyca/cryptography), used as known answers.
+*/
+fn fixture_key() -> RsaKeyPair<Scalable> {
+    let p = Scalable::from_hex(
+        "d453e4b999ecdb35990abb43e96f25051719a80bd7715ae1ec95f5c2d22a095b\
+         03401e99a9edf4d4551dc6db38ab7d267f6276908700567421ccb5f3cb898d97",
+    );
+    let q = Scalable::from_hex(
+        "c098681885dc0f342c54a06991bdc6abe1145d86fe1102194028b4e78562c579\
+         06ff95c78baec9105da9741f71c8b603b46b8025635f946d3b92b4e99eb5f2f9",
+    );
+    let e = Scalable::from(RSA_PUBLIC_EXPONENT);
+    RsaKeyPair::from_private(RsaPrivateKey::from_primes(vec![p.unwrap(), q.unwrap()], e).unwrap())
+}
+
+/* Example 1 key of the RFC 8017 test vector files, built from its
+   primes. None when they do not multiply to the published modulus.
+*/
+fn rfc8017_key(n: &str, p: &str, q: &str) -> Option<RsaKeyPair<Scalable>> {
+    let primes = vec![Scalable::from_hex(p)?, Scalable::from_hex(q)?];
+    let private = RsaPrivateKey::from_primes(primes, Scalable::from(RSA_PUBLIC_EXPONENT)).ok()?;
+    (*private.n() == Scalable::from_hex(n)?).then(|| RsaKeyPair::from_private(private))
+}
+
+pub fn test_pkcs1v15() {
+    let keys = fixture_key();
+    let mut rng = SplitMix64::new(14);
+    let cases = vec![
+        b"".to_vec(),
+        b"attack at dawn".to_vec(),
+        vec![0; 117],
+        (0..=116).collect(),
+    ];
+
+    for ele in cases {
+        let c1 = keys.public.encrypt(&ele, &mut rng).unwrap();
+        let c2 = keys.public.encrypt(&ele, &mut rng).unwrap();
+        let result = keys.private.decrypt(&c1);
+        if result.as_ref() == Ok(&ele) && c1 != c2 && c1.len() == 128 {
+            println!(
+                "decrypt_pkcs1v15(encrypt_pkcs1v15({} bytes)) passed",
+                ele.len()
+            );
+        } else {
+            println!(
+                "decrypt_pkcs1v15(encrypt_pkcs1v15({} bytes))={result:?} error: expected {ele:?} and randomized ciphertexts",
+                ele.len()
+            );
+        }
+    }
+
+    let known = hex_bytes(
+        "38054255cc9869254fc3f8cd4289fd8b6f7b5a58acde4ceb4fac0b40615be47c\
+         1cdf938f4cb9ad31b7ebc0adfe7f0317f552d735c13b668ba920a454defa34e8\
+         25aa673e254fb0178009878e3b01c6ec88ef8ccc990ea139ee31413e3e0bc153\
+         5214534732fa6cdd1ca05cbd113eb9393be92f4290c3544b856a1fcce8dff521",
+    );
+    let mut tampered = known.clone();
+    tampered[5] ^= 1;
+    let cases = vec![
+        (known.clone(), Ok(b"attack at dawn".to_vec())),
+        (tampered, Err(RsaError::Decryption)),
+        (known[1..].to_vec(), Err(RsaError::Decryption)),
+        (vec![0xff; 128], Err(RsaError::Decryption)),
+        (
+            keys.public.n().to_bytes_be_len(128).unwrap(),
+            Err(RsaError::Decryption),
+        ),
+    ];
+
+    for ele in cases {
+        let result = decrypt_pkcs1v15(&ele.0, &keys.private);
+        if result == ele.1 {
+            println!("decrypt_pkcs1v15({} bytes)={result:?} passed", ele.0.len());
+        } else {
+            println!(
+                "decrypt_pkcs1v15({} bytes)={result:?} error: expected {:?}",
+                ele.0.len(),
+                ele.1
+            );
+        }
+    }
+
+    let result = keys.public.encrypt(&[0; 118], &mut rng);
+    if result == Err(RsaError::MessageTooLong) {
+        println!("encrypt_pkcs1v15(118 bytes)={result:?} passed");
+    } else {
+        println!("encrypt_pkcs1v15(118 bytes)={result:?} error: expected Err(MessageTooLong)");
+    }
+}
+
+pub fn test_generate_rsa_key_pair() {
+    let cases = vec![(32, 1), (64, 2), (127, 3), (256, 4), (512, 5), (1024, 6)];
+
+    for ele in cases {
+        let (bits, seed) = ele;
+        let mut rng = SplitMix64::new(seed);
+        let keys = RsaKeyPair::generate(bits, &mut rng);
+        let m = modulo(&random_bits(bits, &mut rng), keys.public.n());
+        let round_trip = decrypt_rsa(&encrypt_rsa(&m, &keys.public), &keys.private);
+        let size = keys.public.size_bits();
+        if size == bits && round_trip == m {
+            println!("RsaKeyPair::generate({bits})=n of {size} bits passed");
+        } else {
+            println!(
+                "RsaKeyPair::generate({bits})=n of {size} bits, round trip {} error: expected {bits} bits, true",
+                round_trip == m
+            );
+        }
+    }
+}
+
+pub fn test_strong_prime_rsa() {
+    let cases = vec![((256, 2), 17), ((512, 2), 18), ((512, 3), 19)];
+
+    for ele in cases {
+        let ((bits, k), seed) = ele;
+        let mut rng = SplitMix64::new(seed);
+        let keys = RsaKeyPair::generate_with(bits, k, PrimeKind::Strong, &mut rng);
+        let m = modulo(&random_bits(bits, &mut rng), keys.public.n());
+        let result = decrypt_rsa(&encrypt_rsa(&m, &keys.public), &keys.private);
+        let primes = keys.private.primes().len();
+        if result == m && primes == k && keys.public.size_bits() == bits {
+            println!("RsaKeyPair::generate_with({bits},{k},Strong) round trip passed");
+        } else {
+            println!(
+                "RsaKeyPair::generate_with({bits},{k},Strong) round trip error: expected {m}, got {result}"
+            );
+        }
+    }
+}
+
+pub fn test_multi_prime_rsa() {
+    let cases = vec![((256, 2), 7), ((256, 3), 8), ((512, 3), 9), ((1024, 4), 10)];
+
+    for ele in cases {
+        let ((bits, k), seed) = ele;
+        let mut rng = SplitMix64::new(seed);
+        let keys = RsaKeyPair::generate_multi_prime(bits, k, &mut rng);
+        let m = modulo(&random_bits(bits, &mut rng), keys.public.n());
+        let c = encrypt_rsa(&m, &keys.public);
+        let result = decrypt_rsa(&c, &keys.private);
+        let plain = mod_power(&c, keys.private.d(), keys.private.n());
+        let primes = keys.private.primes().len();
+        if result == m && result == plain && primes == k && keys.public.size_bits() == bits {
+            println!("RsaKeyPair::generate_multi_prime({bits},{k}) round trip passed");
+        } else {
+            println!(
+                "RsaKeyPair::generate_multi_prime({bits},{k}) round trip error: expected {m}, got {result} with CRT and {plain} without"
+            );
+        }
+    }
+}
+
+pub fn test_sign_rsa() {
+    let cases = vec![(128, 11), (512, 12), (1024, 13)];
+
+    for ele in cases {
+        let (bits, seed) = ele;
+        let keys = RsaKeyPair::generate(bits, &mut SplitMix64::new(seed));
+        let s = sign_rsa_message(b"attack at dawn", &keys.private);
+        let forged = modulo(&(&s + 1), keys.public.n());
+        let result = (
+            verify_rsa_message(b"attack at dawn", &s, &keys.public),
+            verify_rsa_message(b"attack at dusk", &s, &keys.public),
+            verify_rsa_message(b"attack at dawn", &forged, &keys.public),
+        );
+        if result == (true, false, false) {
+            println!("sign_rsa_message({bits} bits key) passed");
+        } else {
+            println!(
+                "sign_rsa_message({bits} bits key) error: expected (true, false, false) on (genuine, tampered message, tampered signature), got {result:?}"
+            );
+        }
+    }
+}
+
+pub fn test_oaep() {
+    let keys = fixture_key();
+    let msg = b"attack at dawn".to_vec();
+    let cases = vec![
+        (
+            (
+                HashFunction::Sha1,
+                b"".to_vec(),
+                (1..=20).collect::<Vec<u8>>(),
+            ),
+            hex_bytes(
+                "46ba2077931c6c33841b507c2adbc7d5696bb77aba7f649a3feb31f17c3978ce\
+                 2dea89d03f3903079b5e9466bea3c50c9f094e33ccc0cf6aa2c07d4c3edd69f4\
+                 5b42de8df63458547d311993d001a6deb17e3f197d22f37ceab5afb1309b8400\
+                 e2c0129b6f17333249fafbc4cfdc16c242f4969cf6ffc2cb37243a6104f67d29",
+            ),
+        ),
+        (
+            (HashFunction::Sha256, b"afit".to_vec(), (1..=32).collect()),
+            hex_bytes(
+                "52ef099dfef4e30cc6ed212ed7c08a4ad0f07a0d22d614ae1310fcee62bf7685\
+                 c9edec0d268f9e335b958f9a7197484a7a943304d39cca31489fc4a92d82be71\
+                 f71777cbc2da97d19cd6508203fd5669a805276e7aa9a62e371570b93497249f\
+                 1f1e962b8e7cce23e987a8f8c31bafc2259370441850b2668bbe66d7a83ea67c",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let ((hash, label, seed), exp) = ele;
+        let result = encrypt_oaep(&msg, &keys.public, hash, &label, &mut FixedBytes(seed));
+        if result.as_ref() == Ok(&exp) {
+            println!("encrypt_oaep({hash:?},{label:?}) known answer passed");
+        } else {
+            println!("encrypt_oaep({hash:?},{label:?})={result:?} error: expected {exp:?}");
+        }
+    }
+
+    let known_sha1 = hex_bytes(
+        "16d00d4a710128704a244399e7dfccde4f74ce042c11a9c9092d354504043f49\
+         1a893132e3124778af29329808fa1671983f801f1b38905777926847b83dae65\
+         62cbebeb81b85f889a0db22773df95bc9ba9685ac16b1491e6b99cb06a7d5ceb\
+         035e2edeb64c952a6d1a0d1d5fa25a8c5b1049d6f2cf8e46f79ff3c485b2a0ca",
+    );
+    let known_sha256 = hex_bytes(
+        "5cfd92df63c96fecb15668648917f6ab64a8583f1c7b11c12ca47b1dc0cb9e23\
+         3b24774d79396311aea2611b8bb430fc646a7b12d9dff40ee7832ae8b1749c0b\
+         cb179c516afde3cd19c63572f0276e9b4927c6b68e345068bc84d938776f452f\
+         7de8b534c0a4a4b5c047ecf99cc25b63a4fd10080979c929a02d5fc01f96a320",
+    );
+    let mut tampered = known_sha1.clone();
+    tampered[100] ^= 0x80;
+    let cases = vec![
+        (
+            (known_sha1.clone(), HashFunction::Sha1, b"".to_vec()),
+            Ok(msg.clone()),
+        ),
+        (
+            (known_sha256.clone(), HashFunction::Sha256, b"afit".to_vec()),
+            Ok(msg.clone()),
+        ),
+        (
+            (known_sha256, HashFunction::Sha256, b"afiT".to_vec()),
+            Err(RsaError::Decryption),
+        ),
+        (
+            (known_sha1.clone(), HashFunction::Sha256, b"".to_vec()),
+            Err(RsaError::Decryption),
+        ),
+        (
+            (tampered, HashFunction::Sha1, b"".to_vec()),
+            Err(RsaError::Decryption),
+        ),
+        (
+            (known_sha1[1..].to_vec(), HashFunction::Sha1, b"".to_vec()),
+            Err(RsaError::Decryption),
+        ),
+    ];
+
+    for ele in cases {
+        let ((c, hash, label), exp) = ele;
+        let result = decrypt_oaep(&c, &keys.private, hash, &label);
+        if result == exp {
+            println!("decrypt_oaep({hash:?},{label:?})={result:?} passed");
+        } else {
+            println!("decrypt_oaep({hash:?},{label:?})={result:?} error: expected {exp:?}");
+        }
+    }
+
+    let mut rng = SplitMix64::new(15);
+    let cases = vec![(HashFunction::Sha1, 86), (HashFunction::Sha256, 62)];
+
+    for ele in cases {
+        let (hash, max_len) = ele;
+        let longest: Vec<u8> = (0..max_len as u8).collect();
+        let c = encrypt_oaep(&longest, &keys.public, hash, b"", &mut rng).unwrap();
+        let result = (
+            decrypt_oaep(&c, &keys.private, hash, b""),
+            encrypt_oaep(&[0; 128][..max_len + 1], &keys.public, hash, b"", &mut rng),
+        );
+        if result == (Ok(longest.clone()), Err(RsaError::MessageTooLong)) {
+            println!("encrypt_oaep({hash:?}) with {max_len} bytes limit passed");
+        } else {
+            println!("encrypt_oaep({hash:?})={result:?} error: expected {max_len} bytes limit");
+        }
+    }
+}
+
+/* RSAES-OAEP Example 1 of the RFC 8017 vector file oaep-vect.txt,
+   SHA-1 for both label hash and MGF1, with an empty label.
+*/
+pub fn test_oaep_vectors() {
+    let keys = rfc8017_key(
+        "a8b3b284af8eb50b387034a860f146c4919f318763cd6c5598c8ae4811a1e0ab\
+         c4c7e0b082d693a5e7fced675cf4668512772c0cbc64a742c6c630f533c8cc72\
+         f62ae833c40bf25842e984bb78bdbf97c0107d55bdb662f5c4e0fab9845cb514\
+         8ef7392dd3aaff93ae1e6b667bb3d4247616d4f5ba10d4cfd226de88d39f16fb",
+        "d32737e7267ffe1341b2d5c0d150a81b586fb3132bed2f8d5262864a9cb9f30a\
+         f38be448598d413a172efb802c21acf1c11c520c2f26a471dcad212eac7ca39d",
+        "cc8853d1d54da630fac004f471f281c7b8982d8224a490edbeb33d3e3d5cc93c\
+         4765703d1dd791642f1f116a0dd852be2419b2af72bfe9a030e860b0288b5d77",
+    )
+    .expect("primes of example 1 multiply to its modulus");
+    let cases = vec![
+        (
+            "1.1",
+            hex_bytes("6628194e12073db03ba94cda9ef9532397d50dba79b987004afefe34"),
+            hex_bytes("18b776ea21069d69776a33e96bad48e1dda0a5ef"),
+            hex_bytes(
+                "354fe67b4a126d5d35fe36c777791a3f7ba13def484e2d3908aff722fad468fb\
+                 21696de95d0be911c2d3174f8afcc201035f7b6d8e69402de5451618c21a535f\
+                 a9d7bfc5b8dd9fc243f8cf927db31322d6e881eaa91a996170e657a05a266426\
+                 d98c88003f8477c1227094a0d9fa1e8c4024309ce1ecccb5210035d47ac72e8a",
+            ),
+        ),
+        (
+            "1.2",
+            hex_bytes("750c4047f547e8e41411856523298ac9bae245efaf1397fbe56f9dd5"),
+            hex_bytes("0cc742ce4a9b7f32f951bcb251efd925fe4fe35f"),
+            hex_bytes(
+                "640db1acc58e0568fe5407e5f9b701dff8c3c91e716c536fc7fcec6cb5b71c11\
+                 65988d4a279e1577d730fc7a29932e3f00c81515236d8d8e31017a7a09df4352\
+                 d904cdeb79aa583adcc31ea698a4c05283daba9089be5491f67c1a4ee48dc74b\
+                 bbe6643aef846679b4cb395a352d5ed115912df696ffe0702932946d71492b44",
+            ),
+        ),
+        (
+            "1.3",
+            hex_bytes(
+                "d94ae0832e6445ce42331cb06d531a82b1db4baad30f746dc916df24d4e3c245\
+                 1fff59a6423eb0e1d02d4fe646cf699dfd818c6e97b051",
+            ),
+            hex_bytes("2514df4695755a67b288eaf4905c36eec66fd2fd"),
+            hex_bytes(
+                "423736ed035f6026af276c35c0b3741b365e5f76ca091b4e8c29e2f0befee603\
+                 595aa8322d602d2e625e95eb81b2f1c9724e822eca76db8618cf09c5343503a4\
+                 360835b5903bc637e3879fb05e0ef32685d5aec5067cd7cc96fe4b2670b6eac3\
+                 066b1fcf5686b68589aafb7d629b02d8f8625ca3833624d4800fb081b1cf94eb",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let (example, msg, seed, exp) = ele;
+        let result = (
+            encrypt_oaep(
+                &msg,
+                &keys.public,
+                HashFunction::Sha1,
+                b"",
+                &mut FixedBytes(seed),
+            ),
+            decrypt_oaep(&exp, &keys.private, HashFunction::Sha1, b""),
+        );
+        if result == (Ok(exp.clone()), Ok(msg.clone())) {
+            println!("oaep-vect example {example} passed");
+        } else {
+            println!("oaep-vect example {example}={result:?} error: expected {exp:?}");
+        }
+    }
+}
+
+pub fn test_pss() {
+    let keys = fixture_key();
+    let msg = b"attack at dawn";
+    let cases = vec![
+        (
+            (HashFunction::Sha256, (1..=32).collect::<Vec<u8>>()),
+            hex_bytes(
+                "9cbea57c2e6dc981211df23b20d34cc65532f005ce2682c6c3d1aab19e7288a9\
+                 2e7dcb81a8a484696c8c69d671c8c3cde9744301c84030bb7a4ece00f062f610\
+                 565969467b0183be372b82b5aac5281634d4a2b8b477b2fda14130d7b9022fa6\
+                 4ffe61fa1235cadc5d74333be685924593e3200ce4dc440f5253375fb6f310a8",
+            ),
+        ),
+        (
+            (HashFunction::Sha1, (1..=20).collect()),
+            hex_bytes(
+                "440398350ab3eeb402f198f05fd299edf6a76c53d67bc242da82c0ce87b33e90\
+                 5320535d3af6283beb59d0dcfec42e10254851367b731a4b4afaf4c66852c0ce\
+                 68905ff8d1493a95ad895d0cbf7dc27225547363c301a92a2fc03564f55b001c\
+                 99bb7f434d8234035ca48b92953fa9d3befc64a0d324cd35c4626018b01979ff",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let ((hash, salt), exp) = ele;
+        let salt_len = salt.len();
+        let result = sign_pss(msg, &keys.private, hash, salt_len, &mut FixedBytes(salt));
+        if result.as_ref() == Ok(&exp) {
+            println!("sign_pss({hash:?},{salt_len}) known answer passed");
+        } else {
+            println!("sign_pss({hash:?},{salt_len})={result:?} error: expected {exp:?}");
+        }
+    }
+
+    let known_sha256 = hex_bytes(
+        "7022ea1a546634e8f4edbb6f9a345f07ebb91e3485588f74c9d8f9d009e4624d\
+         092bc16b317574860a583e9dc10079d2e635fec019995a7560ee6b9db9968fb9\
+         0aad9e4ee6d36f84cf4fbace106ddbcb14e3079a4ff3fcd33123d2c1f53c87a4\
+         bb13aebbfb20d017b0ddd25de90525011ff358d553c1c30a1766c63314be66c3",
+    );
+    let known_sha1 = hex_bytes(
+        "95491a341a4a9a2db8596d41a912e0a6ebdb97fca4a1dc4ab00ad6abff70c019\
+         59c3c714ea986fa169c936d8eba326d633f3240198c4dea4f4c0b038efa163e0\
+         f68908019fb2f688b54aad4f6d2e7a70bc87dba046aa5d49c2c1f463dfde5cf4\
+         299a054920b5175f3ce07092fce8851dda3d8dde56bdb6a2f767e6cbf5298749",
+    );
+    let known_no_salt = hex_bytes(
+        "28ae62eca7267c02582d8088dfbd0a488980bb9c608d8b8e2ad47b81bf9cadcf\
+         b92bf6de0960d6a40488de6820635b029ff978d007fe4f4fc7f9da4b276f6e12\
+         4e63f772d31fdb27f7590e1b7b889d91458388852307c239e09b60af7c2d6887\
+         669916de09ab87d9463a9fbda77ab392f567e77da2dfb92419551b19908b0e2e",
+    );
+    let mut tampered = known_sha256.clone();
+    tampered[64] ^= 4;
+    let cases = vec![
+        (
+            (msg.to_vec(), known_sha256.clone(), HashFunction::Sha256, 32),
+            true,
+        ),
+        (
+            (msg.to_vec(), known_sha1.clone(), HashFunction::Sha1, 20),
+            true,
+        ),
+        ((msg.to_vec(), known_no_salt, HashFunction::Sha256, 0), true),
+        (
+            (
+                b"attack at dusk".to_vec(),
+                known_sha256.clone(),
+                HashFunction::Sha256,
+                32,
+            ),
+            false,
+        ),
+        ((msg.to_vec(), tampered, HashFunction::Sha256, 32), false),
+        (
+            (msg.to_vec(), known_sha256.clone(), HashFunction::Sha256, 20),
+            false,
+        ),
+        ((msg.to_vec(), known_sha1, HashFunction::Sha256, 20), false),
+        (
+            (
+                msg.to_vec(),
+                known_sha256[1..].to_vec(),
+                HashFunction::Sha256,
+                32,
+            ),
+            false,
+        ),
+    ];
+
+    for ele in cases {
+        let ((m, s, hash, salt_len), exp) = ele;
+        let result = verify_pss(&m, &s, &keys.public, hash, salt_len);
+        if result == exp {
+            println!(
+                "verify_pss({:?},{hash:?},{salt_len})={result} passed",
+                String::from_utf8_lossy(&m)
+            );
+        } else {
+            println!(
+                "verify_pss({:?},{hash:?},{salt_len})={result} error: expected {exp}",
+                String::from_utf8_lossy(&m)
+            );
+        }
+    }
+
+    let mut rng = SplitMix64::new(16);
+    let s1 = sign_pss(msg, &keys.private, HashFunction::Sha256, 32, &mut rng).unwrap();
+    let s2 = sign_pss(msg, &keys.private, HashFunction::Sha256, 32, &mut rng).unwrap();
+    let result = (
+        s1 != s2,
+        verify_pss(msg, &s1, &keys.public, HashFunction::Sha256, 32),
+        verify_pss(msg, &s2, &keys.public, HashFunction::Sha256, 32),
+    );
+    if result == (true, true, true) {
+        println!("sign_pss twice gives two valid signatures passed");
+    } else {
+        println!("sign_pss twice={result:?} error: expected (true, true, true)");
+    }
+
+    let result = sign_pss(msg, &keys.private, HashFunction::Sha256, 95, &mut rng);
+    if result == Err(RsaError::KeyTooSmall) {
+        println!("sign_pss(salt of 95 bytes)={result:?} passed");
+    } else {
+        println!("sign_pss(salt of 95 bytes)={result:?} error: expected Err(KeyTooSmall)");
+    }
+}
+
+/* RSASSA-PSS Example 1 of the RFC 8017 vector file pss-vect.txt,
+   SHA-1 for both message digest and MGF1, with 20 bytes salts.
+*/
+pub fn test_pss_vectors() {
+    let keys = rfc8017_key(
+        "a56e4a0e701017589a5187dc7ea841d156f2ec0e36ad52a44dfeb1e61f7ad991\
+         d8c51056ffedb162b4c0f283a12a88a394dff526ab7291cbb307ceabfce0b1df\
+         d5cd9508096d5b2b8b6df5d671ef6377c0921cb23c270a70e2598e6ff89d19f1\
+         05acc2d3f0cb35f29280e1386b6f64c4ef22e1e1f20d0ce8cffb2249bd9a2137",
+        "e7e8942720a877517273a356053ea2a1bc0c94aa72d55c6e86296b2dfc967948\
+         c0a72cbccca7eacb35706e09a1df55a1535bd9b3cc34160b3b6dcd3eda8e6443",
+        "b69dca1cf7d4d7ec81e75b90fcca874abcde123fd2700180aa90479b6e48de8d\
+         67ed24f9f19d85ba275874f542cd20dc723e6963364a1f9425452b269a6799fd",
+    )
+    .expect("primes of example 1 multiply to its modulus");
+    let cases = vec![
+        (
+            "1.1",
+            hex_bytes(
+                "cdc87da223d786df3b45e0bbbc721326d1ee2af806cc315475cc6f0d9c66e1b6\
+                 2371d45ce2392e1ac92844c310102f156a0d8d52c1f4c40ba3aa65095786cb76\
+                 9757a6563ba958fed0bcc984e8b517a3d5f515b23b8a41e74aa867693f90dfb0\
+                 61a6e86dfaaee64472c00e5f20945729cbebe77f06ce78e08f4098fba41f9d61\
+                 93c0317e8b60d4b6084acb42d29e3808a3bc372d85e331170fcbf7cc72d0b71c\
+                 296648b3a4d10f416295d0807aa625cab2744fd9ea8fd223c42537029828bd16\
+                 be02546f130fd2e33b936d2676e08aed1b73318b750a0167d0",
+            ),
+            hex_bytes("dee959c7e06411361420ff80185ed57f3e6776af"),
+            hex_bytes(
+                "9074308fb598e9701b2294388e52f971faac2b60a5145af185df5287b5ed2887\
+                 e57ce7fd44dc8634e407c8e0e4360bc226f3ec227f9d9e54638e8d31f5051215\
+                 df6ebb9c2f9579aa77598a38f914b5b9c1bd83c4e2f9f382a0d0aa3542ffee65\
+                 984a601bc69eb28deb27dca12c82c2d4c3f66cd500f1ff2b994d8a4e30cbb33c",
+            ),
+        ),
+        (
+            "1.2",
+            hex_bytes(
+                "851384cdfe819c22ed6c4ccb30daeb5cf059bc8e1166b7e3530c4c233e2b5f8f\
+                 71a1cca582d43ecc72b1bca16dfc7013226b9e",
+            ),
+            hex_bytes("ef2869fa40c346cb183dab3d7bffc98fd56df42d"),
+            hex_bytes(
+                "3ef7f46e831bf92b32274142a585ffcefbdca7b32ae90d10fb0f0c729984f04e\
+                 f29a9df0780775ce43739b97838390db0a5505e63de927028d9d29b219ca2c45\
+                 17832558a55d694a6d25b9dab66003c4cccd907802193be5170d26147d37b935\
+                 90241be51c25055f47ef62752cfbe21418fafe98c22c4d4d47724fdb5669e843",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let (example, msg, salt, exp) = ele;
+        let result = (
+            sign_pss(
+                &msg,
+                &keys.private,
+                HashFunction::Sha1,
+                20,
+                &mut FixedBytes(salt),
+            ),
+            verify_pss(&msg, &exp, &keys.public, HashFunction::Sha1, 20),
+        );
+        if result == (Ok(exp.clone()), true) {
+            println!("pss-vect example {example} passed");
+        } else {
+            println!("pss-vect example {example}={result:?} error: expected {exp:?}");
+        }
+    }
+}
+
+pub fn test_ciphers() {
+    test_generate_rsa_key_pair();
+    println!();
+    test_multi_prime_rsa();
+    println!();
+    test_strong_prime_rsa();
+    println!();
+    test_sign_rsa();
+    println!();
+    test_pkcs1v15();
+    println!();
+    test_oaep();
+    println!();
+    test_oaep_vectors();
+    println!();
+    test_pss();
+    println!();
+    test_pss_vectors();
+    println!();
+}
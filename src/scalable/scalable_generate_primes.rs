@@ -1 +1,882 @@
+use crate::builtin::{generate_primes::eratosthenes, test_primes::is_prime};
+use crate::hash::sha256::sha256;
+use crate::rng::{ChaCha20Rng, Rng, SplitMix64, random_bits, uniform_scalable};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc;
+use std::thread;
 
+use super::{
+    scalable_basic_arithmetics::gcd,
+    scalable_certificates::{
+        Certificate, PRATT_BITS, PrimeFactor, pocklington_certificate, pratt_certificate,
+        verify_certificate,
+    },
+    scalable_power::mod_power,
+    scalable_test_primes::{
+        baillie_psw, is_strong_pseudo_prime, lucas_lehmer, miller_rabin, pepin, proth,
+    },
+    scalables::{Scalable, modulo},
+};
+
+/* Bound on small primes used to filter out candidates. */
+const SMALL_PRIMES_LIMIT: i64 = 2000;
+
+/* Miller-Rabin rounds run on candidates surviving trial division. */
+const MILLER_RABIN_ROUNDS: usize = 20;
+
+/* Number of odd candidates sieved at once by `random_prime_incremental`. */
+const SIEVE_WINDOW: usize = 2048;
+
+/* Trial division by small primes, true when none divides n. */
+fn no_small_factor(n: &Scalable, small_primes: &[i64]) -> bool {
+    small_primes
+        .iter()
+        .all(|q| n.rem_u64(*q as u64) != 0 || *n == *q)
+}
+
+/* Random odd integer of exactly given bit size.
+   @param bits size of the integer, at least 2.
+   @param rng random generator.
+*/
+fn random_odd(bits: usize, rng: &mut impl Rng) -> Scalable {
+    let mut n = random_bits(bits, rng);
+    n.set_bit(bits - 1);
+    n.set_bit(0);
+    n
+}
+
+/* Random prime of exactly given bit size. Odd candidates are drawn
+   until one has no factor among small primes and passes Miller-Rabin.
+   @param bits size of the prime, at least 2.
+   @param rng random generator.
+*/
+pub fn random_prime(bits: usize, rng: &mut impl Rng) -> Scalable {
+    if bits < 2 {
+        panic!("bits must be >= 2");
+    }
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    loop {
+        let n = random_odd(bits, rng);
+        if no_small_factor(&n, &small_primes) && miller_rabin(&n, MILLER_RABIN_ROUNDS, rng) {
+            return n;
+        }
+    }
+}
+
+/* Random prime of exactly given bit size searched by workers threads,
+   each drawing candidates as `random_prime` does from its own ChaCha20
+   generator seeded by rng. The first prime found is returned and the
+   other workers stop at their next candidate.
+   @param bits size of the prime, at least 2.
+   @param workers number of threads, at least 1.
+   @param rng random generator seeding the workers.
+*/
+pub fn parallel_random_prime(bits: usize, workers: usize, rng: &mut impl Rng) -> Scalable {
+    if bits < 2 {
+        panic!("bits must be >= 2");
+    }
+    if workers == 0 {
+        panic!("workers must be >= 1");
+    }
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    let found = AtomicBool::new(false);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            let mut seed = [0; 32];
+            rng.fill_bytes(&mut seed);
+            let mut worker_rng = ChaCha20Rng::from_seed(seed);
+            let (small_primes, found, sender) = (&small_primes, &found, sender.clone());
+            scope.spawn(move || {
+                while !found.load(Ordering::Relaxed) {
+                    let n = random_odd(bits, &mut worker_rng);
+                    if no_small_factor(&n, small_primes)
+                        && miller_rabin(&n, MILLER_RABIN_ROUNDS, &mut worker_rng)
+                    {
+                        found.store(true, Ordering::Relaxed);
+                        let _ = sender.send(n);
+                    }
+                }
+            });
+        }
+        receiver.recv().unwrap()
+    })
+}
+
+/* Mark candidates start + 2i of a window congruent to target modulo
+   the odd prime q, i.e. those with i = (target - start) / 2 mod q.
+   @param window marks of the candidates.
+   @param start_mod_q remainder of the first candidate.
+   @param target excluded remainder.
+   @param q odd prime.
+*/
+fn sieve_residue(window: &mut [bool], start_mod_q: u64, target: u64, q: u64) {
+    let mut i = ((target + q - start_mod_q) % q * q.div_ceil(2) % q) as usize;
+    while i < window.len() {
+        window[i] = true;
+        i += q as usize;
+    }
+}
+
+/* Random prime of exactly given bit size, searched incrementally from
+   a random odd start. A window of consecutive odd candidates is sieved
+   at once: for each small prime q, a single remainder of the start
+   locates every multiple of q in the window. Survivors are then tested
+   with Miller-Rabin in increasing order. Faster than `random_prime`,
+   though primes following large gaps are slightly more likely.
+   @param bits size of the prime, at least 2.
+   @param rng random generator.
+*/
+pub fn random_prime_incremental(bits: usize, rng: &mut impl Rng) -> Scalable {
+    if bits < 2 {
+        panic!("bits must be >= 2");
+    }
+    if bits <= 16 {
+        return random_prime(bits, rng);
+    }
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    loop {
+        let start = random_odd(bits, rng);
+        let mut composite = vec![false; SIEVE_WINDOW];
+        for q in small_primes.iter().skip(1).map(|q| *q as u64) {
+            sieve_residue(&mut composite, start.rem_u64(q), 0, q);
+        }
+        for (i, _) in composite.iter().enumerate().filter(|(_, c)| !**c) {
+            let n = &start + (2 * i) as i64;
+            if n.bits() != bits {
+                break;
+            }
+            if miller_rabin(&n, MILLER_RABIN_ROUNDS, rng) {
+                return n;
+            }
+        }
+    }
+}
+
+/* Random safe prime p = 2q + 1 of exactly given bit size, q being a
+   Sophie Germain prime, given as the couple (q, p) like
+   `double_primes`. Such primes give the groups of ElGamal and
+   Diffie-Hellman, where the order of most elements is q or 2q.
+   Candidates for q are sieved by windows as in
+   `random_prime_incremental`, a small prime r ruling out both
+   q = 0 and q = (r - 1) / 2 mod r, the latter meaning r divides 2q + 1.
+   Survivors go through a single base 2 round for q and p before the
+   full Miller-Rabin test.
+   @param bits size of p, at least 3.
+   @param rng random generator.
+*/
+pub fn random_safe_prime(bits: usize, rng: &mut impl Rng) -> (Scalable, Scalable) {
+    if bits < 3 {
+        panic!("bits must be >= 3");
+    }
+    let two = Scalable::from(2i64);
+    let is_safe = |q: &Scalable, p: &Scalable, rng: &mut _| {
+        is_strong_pseudo_prime(q, &two)
+            && is_strong_pseudo_prime(p, &two)
+            && miller_rabin(q, MILLER_RABIN_ROUNDS, rng)
+            && miller_rabin(p, MILLER_RABIN_ROUNDS, rng)
+    };
+    if bits <= 16 {
+        loop {
+            let q = random_odd(bits - 1, rng);
+            let p = &q * 2 + 1;
+            if miller_rabin(&q, MILLER_RABIN_ROUNDS, rng)
+                && miller_rabin(&p, MILLER_RABIN_ROUNDS, rng)
+            {
+                return (q, p);
+            }
+        }
+    }
+
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    loop {
+        let start = random_odd(bits - 1, rng);
+        let mut composite = vec![false; SIEVE_WINDOW];
+        for r in small_primes.iter().skip(1).map(|r| *r as u64) {
+            let start_mod_r = start.rem_u64(r);
+            sieve_residue(&mut composite, start_mod_r, 0, r);
+            sieve_residue(&mut composite, start_mod_r, (r - 1) / 2, r);
+        }
+        for (i, _) in composite.iter().enumerate().filter(|(_, c)| !**c) {
+            let q = &start + (2 * i) as i64;
+            if q.bits() != bits - 1 {
+                break;
+            }
+            let p = &q * 2 + 1;
+            if is_safe(&q, &p, rng) {
+                return (q, p);
+            }
+        }
+    }
+}
+
+/* Strong prime p with the large prime factors making it strong: r
+   divides p - 1, s divides p + 1 and t divides r - 1, so that neither
+   Pollard's p - 1 nor Williams' p + 1 factoring methods apply.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrongPrime {
+    pub p: Scalable,
+    pub r: Scalable,
+    pub s: Scalable,
+    pub t: Scalable,
+}
+
+/* Trial division by small primes followed by Miller-Rabin. */
+fn is_probable_prime(n: &Scalable, small_primes: &[i64], rng: &mut impl Rng) -> bool {
+    no_small_factor(n, small_primes) && miller_rabin(n, MILLER_RABIN_ROUNDS, rng)
+}
+
+/* Random strong prime of exactly given bit size with its two most
+   significant bits set, by Gordon's algorithm:
+   1. random primes s and t of about half the size,
+   2. r is the first prime of the form 2it + 1,
+   3. p0 = 2 (s^(r - 2) mod r) s - 1, so that p0 = 1 mod r and
+      p0 = -1 mod s,
+   4. p is the first prime of the form p0 + 2jrs in the target range.
+   @param bits size of p, at least 64.
+   @param rng random generator.
+*/
+pub fn strong_prime(bits: usize, rng: &mut impl Rng) -> StrongPrime {
+    if bits < 64 {
+        panic!("bits must be >= 64");
+    }
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    let lower = Scalable::pow2(bits - 1) + Scalable::pow2(bits - 2);
+    let upper = Scalable::pow2(bits);
+    loop {
+        let s = random_prime(bits / 2 - 8, rng);
+        let t = random_prime(bits / 2 - 12, rng);
+
+        let mut r = &(&Scalable::pow2(bits / 2 - 5) / &t) * &t * 2 + 1;
+        while !is_probable_prime(&r, &small_primes, rng) {
+            r = r + &t * 2;
+        }
+
+        let p0 = &(&mod_power(&s, &(&r - 2), &r) * &s) * 2 - 1;
+        let step = &(&r * &s) * 2;
+        let j0 = (&(&lower - &p0) + &step - 1) / &step;
+        let mut p = &p0 + &(&j0 * &step);
+        while p < upper {
+            if is_probable_prime(&p, &small_primes, rng) {
+                return StrongPrime { p, r, s, t };
+            }
+            p = p + &step;
+        }
+    }
+}
+
+/* Large prime factors found by `check_strong_prime`, None when the
+   corresponding factor could not be exhibited.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StrongPrimeReport {
+    pub p_minus_one: Option<Scalable>,
+    pub p_plus_one: Option<Scalable>,
+    pub r_minus_one: Option<Scalable>,
+}
+
+impl StrongPrimeReport {
+    /* Every required large prime factor has been exhibited. */
+    pub fn is_strong(&self) -> bool {
+        self.p_minus_one.is_some() && self.p_plus_one.is_some() && self.r_minus_one.is_some()
+    }
+}
+
+/* Prime factor of m with at least min_bits bits: a hint dividing m, or
+   else what remains of m once small primes are divided out.
+*/
+fn large_prime_factor(
+    m: &Scalable,
+    hints: &[Scalable],
+    min_bits: usize,
+    small_primes: &[i64],
+) -> Option<Scalable> {
+    let is_large_prime = |x: &Scalable| x.bits() >= min_bits && baillie_psw(x);
+    if let Some(h) = hints
+        .iter()
+        .find(|h| h.bits() > 1 && modulo(m, h).is_zero() && is_large_prime(h))
+    {
+        return Some(h.clone());
+    }
+    let mut cofactor = m.clone();
+    for q in small_primes {
+        while cofactor.rem_u64(*q as u64) == 0 {
+            cofactor = &cofactor / *q;
+        }
+    }
+    if is_large_prime(&cofactor) {
+        Some(cofactor)
+    } else {
+        None
+    }
+}
+
+/* Report whether p is a strong prime: p - 1 has a prime factor r,
+   p + 1 has a prime factor s and r - 1 has a prime factor t, all of at
+   least min_bits bits. Factors are looked for among hints, such as the
+   r, s and t given by `strong_prime`, and otherwise by dividing out
+   primes below 2000, so a strong prime given without hints may be
+   reported weak but a weak prime is never reported strong.
+   @param p prime to check.
+   @param hints candidate prime factors.
+   @param min_bits minimal size of the factors.
+*/
+pub fn check_strong_prime(p: &Scalable, hints: &[Scalable], min_bits: usize) -> StrongPrimeReport {
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    let p_minus_one = large_prime_factor(&(p - 1), hints, min_bits, &small_primes);
+    let p_plus_one = large_prime_factor(&(p + 1), hints, min_bits, &small_primes);
+    let r_minus_one = p_minus_one
+        .as_ref()
+        .and_then(|r| large_prime_factor(&(r - 1), hints, min_bits, &small_primes));
+    StrongPrimeReport {
+        p_minus_one,
+        p_plus_one,
+        r_minus_one,
+    }
+}
+
+/* Random prime of exactly given bit size together with a certificate
+   proving it prime. Maurer's construction: a certified prime q of about
+   half the size is built recursively, then n = 2Rq + 1 is tried for
+   random R until prime, q > sqrt(n) giving a Pocklington certificate.
+   Below 32 bits the prime is drawn directly and Pratt certified.
+   @param bits size of the prime, at least 2.
+   @param rng random generator.
+*/
+pub fn certified_prime(bits: usize, rng: &mut impl Rng) -> (Scalable, Certificate) {
+    if bits < 2 {
+        panic!("bits must be >= 2");
+    }
+    if bits <= PRATT_BITS {
+        loop {
+            let n = random_odd(bits, rng);
+            if let Some(certificate) = pratt_certificate(&n) {
+                return (n, certificate);
+            }
+        }
+    }
+
+    let small_primes = eratosthenes(SMALL_PRIMES_LIMIT);
+    let (q, q_certificate) = certified_prime(bits / 2 + 2, rng);
+    let lo = &Scalable::pow2(bits - 2) / &q + 1;
+    let range = &Scalable::pow2(bits - 1) / &q - &lo;
+    loop {
+        let r = uniform_scalable(&range, rng) + &lo;
+        let n = &r * &q * 2 + 1;
+        if n.bits() != bits || !no_small_factor(&n, &small_primes) || !baillie_psw(&n) {
+            continue;
+        }
+        let factors = vec![PrimeFactor {
+            q: q.clone(),
+            exponent: 1,
+            certificate: q_certificate.clone(),
+        }];
+        if let Some(certificate) = pocklington_certificate(&n, factors, rng) {
+            return (n, certificate);
+        }
+    }
+}
+
+/* Prime built by the Shawe-Taylor construction, with what is needed to
+   replay it: prime_seed and prime_gen_counter are the values FIPS 186-4
+   returns alongside the prime, prime_seed being the seed from which
+   further primes are derived. The certificate is the Pocklington chain
+   the construction checks along the way.
+*/
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProvablePrime {
+    pub p: Scalable,
+    pub prime_seed: Vec<u8>,
+    pub prime_gen_counter: u64,
+    pub certificate: Certificate,
+}
+
+/* Seed bytes of given length, the seed being reduced modulo 2^(8 len). */
+fn seed_bytes(seed: &Scalable, seed_len: usize) -> Vec<u8> {
+    modulo(seed, &Scalable::pow2(8 * seed_len))
+        .to_bytes_be_len(seed_len)
+        .unwrap()
+}
+
+/* SHA-256(seed + offset), the sum wrapping on the seed length. */
+fn seed_hash(seed: &Scalable, offset: i64, seed_len: usize) -> [u8; 32] {
+    sha256(&seed_bytes(&(seed + offset), seed_len))
+}
+
+/* Concatenation of iterations + 1 seed hashes, the i-th one weighted by
+   2^(256 i), as in steps 19 and 26 of the construction.
+*/
+fn seed_hashes(seed: &Scalable, iterations: usize, seed_len: usize) -> Scalable {
+    (0..=iterations).fold(Scalable::zero(), |acc, i| {
+        acc + (Scalable::from_bytes_be(&seed_hash(seed, i as i64, seed_len)) << (256 * i))
+    })
+}
+
+/* Recursive part of `shawe_taylor_prime`, working on the seed as an
+   integer. Returns the prime, its certificate, the new seed and the
+   generation counter.
+*/
+fn shawe_taylor(
+    bits: usize,
+    seed: &Scalable,
+    seed_len: usize,
+) -> Option<(Scalable, Certificate, Scalable, u64)> {
+    if bits < 33 {
+        let mut prime_seed = seed.clone();
+        let mut counter = 0;
+        loop {
+            let (h0, h1) = (
+                seed_hash(&prime_seed, 0, seed_len),
+                seed_hash(&prime_seed, 1, seed_len),
+            );
+            let h: Vec<u8> = h0.iter().zip(h1.iter()).map(|(x, y)| x ^ y).collect();
+            let mut c = modulo(&Scalable::from_bytes_be(&h), &Scalable::pow2(bits - 1));
+            c.set_bit(bits - 1);
+            c.set_bit(0);
+            counter += 1;
+            prime_seed = prime_seed + 2;
+            if is_prime(c.to_i64().unwrap()) {
+                let certificate = pratt_certificate(&c)?;
+                return Some((c, certificate, prime_seed, counter));
+            }
+            if counter > 4 * bits as u64 {
+                return None;
+            }
+        }
+    }
+
+    let (c0, c0_certificate, mut prime_seed, mut counter) =
+        shawe_taylor(bits.div_ceil(2) + 1, seed, seed_len)?;
+    let iterations = bits.div_ceil(256) - 1;
+    let old_counter = counter;
+    let x = seed_hashes(&prime_seed, iterations, seed_len);
+    prime_seed = prime_seed + (iterations as i64 + 1);
+    let x = modulo(&x, &Scalable::pow2(bits - 1)) + Scalable::pow2(bits - 1);
+    let step = &c0 * 2;
+    let mut t = (&x + &step - 1) / &step;
+    loop {
+        if &(&t * &step) + 1 > Scalable::pow2(bits) {
+            t = (Scalable::pow2(bits - 1) + &step - 1) / &step;
+        }
+        let c = &(&t * &step) + 1;
+        counter += 1;
+        let a = seed_hashes(&prime_seed, iterations, seed_len);
+        prime_seed = prime_seed + (iterations as i64 + 1);
+        let a = modulo(&a, &(&c - 3)) + 2;
+        let z = mod_power(&a, &(&t * 2), &c);
+        if gcd(&(&z - 1), &c).is_one() && mod_power(&z, &c0, &c).is_one() {
+            let certificate = Certificate::Pocklington {
+                n: c.clone(),
+                witness: a,
+                factors: vec![PrimeFactor {
+                    q: c0,
+                    exponent: 1,
+                    certificate: c0_certificate,
+                }],
+            };
+            return Some((c, certificate, prime_seed, counter));
+        }
+        if counter >= 4 * bits as u64 + old_counter {
+            return None;
+        }
+        t = t + 1;
+    }
+}
+
+/* Provable prime of exactly given bit size deterministically derived
+   from a seed, by the Shawe-Taylor random prime routine of FIPS 186-4
+   appendix C.6 with SHA-256. Below 33 bits candidates are hashed from
+   the seed and tested by trial division. Above, a prime c0 of about
+   half the size is built recursively, then c = 2tc0 + 1 is tried for t
+   and a base a hashed from the seed until a^(2t) = z with
+   gcd(z - 1, c) = 1 and z^c0 = 1, which proves c prime since c0 > sqrt(c).
+   The same seed always gives the same prime, so the generation can be
+   replayed and audited. None when the routine reports failure.
+   @param bits size of the prime, at least 2.
+   @param seed non empty input seed.
+*/
+pub fn shawe_taylor_prime(bits: usize, seed: &[u8]) -> Option<ProvablePrime> {
+    if bits < 2 || seed.is_empty() {
+        return None;
+    }
+    let (p, certificate, prime_seed, prime_gen_counter) =
+        shawe_taylor(bits, &Scalable::from_bytes_be(seed), seed.len())?;
+    Some(ProvablePrime {
+        p,
+        prime_seed: seed_bytes(&prime_seed, seed.len()),
+        prime_gen_counter,
+        certificate,
+    })
+}
+
+/* Provable prime of exactly given bit size from a random 256 bits seed,
+   returned with the prime so that `shawe_taylor_prime` can replay it.
+   @param bits size of the prime, at least 2.
+   @param rng random generator.
+*/
+pub fn random_provable_prime(bits: usize, rng: &mut impl Rng) -> (Vec<u8>, ProvablePrime) {
+    if bits < 2 {
+        panic!("bits must be >= 2");
+    }
+    loop {
+        let mut seed = vec![0; 32];
+        rng.fill_bytes(&mut seed);
+        if let Some(prime) = shawe_taylor_prime(bits, &seed) {
+            return (seed, prime);
+        }
+    }
+}
+
+/* Exponents p up to limit such that the Mersenne number 2^p - 1 is
+   prime. Only prime exponents can give Mersenne primes, each being
+   checked with the Lucas-Lehmer test.
+   @param limit positive integer bounding searched for exponents.
+*/
+pub fn mersenne_primes(limit: i64) -> Vec<i64> {
+    eratosthenes(limit)
+        .into_iter()
+        .filter(|p| lucas_lehmer(*p as u64))
+        .collect()
+}
+
+/* Finding Proth primes k * 2^n + 1 for a given k, as couples (n, prime).
+   @param k positive multiplier.
+   @param limit positive integer bounding searched for exponents n.
+*/
+pub fn proth_primes(k: u64, limit: usize) -> Vec<(usize, Scalable)> {
+    let k = Scalable::from(k);
+    let mut result = vec![];
+    for n in k.bits().max(1)..=limit {
+        if proth(&k, n) {
+            result.push((n, (&k << n) + 1));
+        }
+    }
+    result
+}
+
+/* Finding Fermat primes 2^(2^n) + 1, as couples (n, prime).
+   @param limit index bounding searched for Fermat numbers, at most
+   PEPIN_MAX_INDEX.
+*/
+pub fn fermat_primes(limit: u32) -> Vec<(u32, Scalable)> {
+    let mut result = vec![];
+    for n in 0..=limit {
+        if pepin(n) {
+            result.push((n, Scalable::pow2(1usize << n) + 1));
+        }
+    }
+    result
+}
+
+// ========================= TESTING =========================
+
+pub fn test_random_prime() {
+    let cases = vec![2, 3, 8, 16, 17, 64, 256, 512];
+    let mut rng = SplitMix64::new(43);
+
+    for bits in cases {
+        let p = random_prime(bits, &mut rng);
+        let q = random_prime_incremental(bits, &mut rng);
+        let result = p.bits() == bits && q.bits() == bits && baillie_psw(&p) && baillie_psw(&q);
+        if result {
+            println!("random_prime({bits})=({p}, {q}) passed");
+        } else {
+            println!("random_prime({bits})=({p}, {q}) error: expected primes of {bits} bits");
+        }
+    }
+
+    let (a, b, c) = (
+        random_prime(128, &mut SplitMix64::new(1)),
+        random_prime(128, &mut SplitMix64::new(1)),
+        random_prime(128, &mut SplitMix64::new(2)),
+    );
+    if a == b && a != c {
+        println!("random_prime reproducible from seed passed");
+    } else {
+        println!("random_prime reproducible from seed error: {a} {b} {c}");
+    }
+
+    let mut rng = SplitMix64::new(44);
+    let draws: Vec<Scalable> = (0..50).map(|_| random_prime(8, &mut rng)).collect();
+    let result = draws.iter().all(|p| p.bits() == 8 && baillie_psw(p))
+        && draws.iter().any(|p| *p != draws[0]);
+    if result {
+        println!("random_prime(8) over 50 draws passed");
+    } else {
+        println!("random_prime(8) over 50 draws error: {draws:?}");
+    }
+}
+
+pub fn test_parallel_random_prime() {
+    let cases = vec![(2, 1), (64, 1), (256, 4), (512, 8)];
+    let mut rng = SplitMix64::new(50);
+
+    for (bits, workers) in cases {
+        let p = parallel_random_prime(bits, workers, &mut rng);
+        if p.bits() == bits && baillie_psw(&p) {
+            println!("parallel_random_prime({bits},{workers})={p} passed");
+        } else {
+            println!(
+                "parallel_random_prime({bits},{workers})={p} error: expected a {bits} bits prime"
+            );
+        }
+    }
+}
+
+pub fn test_random_safe_prime() {
+    let cases = vec![3, 4, 8, 16, 17, 64, 256, 512];
+    let mut rng = SplitMix64::new(44);
+
+    for bits in cases {
+        let (q, p) = random_safe_prime(bits, &mut rng);
+        let result = p.bits() == bits && p == &q * 2 + 1 && baillie_psw(&q) && baillie_psw(&p);
+        if result {
+            println!("random_safe_prime({bits})=({q}, {p}) passed");
+        } else {
+            println!(
+                "random_safe_prime({bits})=({q}, {p}) error: expected safe prime of {bits} bits"
+            );
+        }
+    }
+}
+
+pub fn test_strong_prime() {
+    let cases = vec![64, 65, 128, 512];
+    let mut rng = SplitMix64::new(45);
+
+    for bits in cases {
+        let sp = strong_prime(bits, &mut rng);
+        let StrongPrime { p, r, s, t } = &sp;
+        let divides = modulo(&(p - 1), r).is_zero()
+            && modulo(&(p + 1), s).is_zero()
+            && modulo(&(r - 1), t).is_zero();
+        let primes = [p, r, s, t].iter().all(|x| baillie_psw(x));
+        let report = check_strong_prime(p, &[r.clone(), s.clone(), t.clone()], bits / 2 - 12);
+        let result = p.bits() == bits && p.bit(bits - 2) && divides && primes && report.is_strong();
+        if result {
+            println!("strong_prime({bits})={p} passed");
+        } else {
+            println!("strong_prime({bits})={sp:?} error: report {report:?}");
+        }
+    }
+}
+
+pub fn test_check_strong_prime() {
+    let mut rng = SplitMix64::new(45);
+    let sp = strong_prime(256, &mut rng);
+    let hints = [sp.r.clone(), sp.s.clone(), sp.t.clone()];
+    let (q, safe) = random_safe_prime(256, &mut rng);
+    let smooth = s_mul(&[
+        3, 2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53,
+    ]) + 1;
+
+    let cases = vec![
+        (
+            ("strong with hints", sp.p.clone(), hints.to_vec()),
+            (true, true, true),
+        ),
+        (
+            ("strong with wrong hints", sp.p.clone(), vec![q.clone()]),
+            (false, false, false),
+        ),
+        (("safe prime", safe, vec![]), (true, false, false)),
+        (("3 * 53# + 1", smooth, vec![]), (false, false, false)),
+    ];
+
+    for ele in cases {
+        let ((name, p, hints), exp) = ele;
+        let report = check_strong_prime(&p, &hints, 100);
+        let result = (
+            report.p_minus_one.is_some(),
+            report.p_plus_one.is_some(),
+            report.r_minus_one.is_some(),
+        );
+        if result == exp && report.is_strong() == (exp == (true, true, true)) {
+            println!("check_strong_prime({name})={result:?} passed");
+        } else {
+            println!("check_strong_prime({name})={result:?} error: expected {exp:?}");
+        }
+    }
+}
+
+fn s_mul(factors: &[i64]) -> Scalable {
+    factors.iter().fold(Scalable::one(), |acc, x| acc * *x)
+}
+
+pub fn test_certified_prime() {
+    let cases = vec![2, 3, 16, 32, 33, 64, 256, 512];
+    let mut rng = SplitMix64::new(39);
+
+    for bits in cases {
+        let (p, certificate) = certified_prime(bits, &mut rng);
+        let parsed = certificate.to_string().parse::<Certificate>();
+        let result = p.bits() == bits
+            && certificate.n() == &p
+            && verify_certificate(&certificate)
+            && parsed.as_ref() == Ok(&certificate)
+            && baillie_psw(&p);
+        if result {
+            println!("certified_prime({bits})={p} passed");
+        } else {
+            println!("certified_prime({bits})={p} error: certificate {certificate:?}");
+        }
+    }
+}
+
+pub fn test_shawe_taylor_prime() {
+    let seed: Vec<u8> = (0..32).collect();
+    let cases = vec![
+        (2, "3", 1, 2),
+        (16, "54709", 6, 12),
+        (32, "2775438527", 5, 10),
+        (33, "4795958243", 13, 19),
+        (64, "9975947422875410411", 17, 24),
+        (
+            256,
+            "109928254445060086659700503570413794044944440159273870781891695052597556044927",
+            294,
+            303,
+        ),
+    ];
+
+    for (bits, p, counter, seed_offset) in cases {
+        let p = Scalable::from_str_radix(p, 10).unwrap();
+        let prime_seed = (Scalable::from_bytes_be(&seed) + seed_offset)
+            .to_bytes_be_len(32)
+            .unwrap();
+        let result = shawe_taylor_prime(bits, &seed);
+        let replayed = shawe_taylor_prime(bits, &seed);
+        let ok = result.as_ref().is_some_and(|x| {
+            x.p == p
+                && x.prime_gen_counter == counter
+                && x.prime_seed == prime_seed
+                && x.certificate.n() == &p
+                && verify_certificate(&x.certificate)
+        }) && result == replayed;
+        if ok {
+            println!("shawe_taylor_prime({bits})={p} passed");
+        } else {
+            println!("shawe_taylor_prime({bits})={result:?} error: expected {p}");
+        }
+    }
+
+    for bits in [0, 1] {
+        let result = shawe_taylor_prime(bits, &seed);
+        if result.is_none() {
+            println!("shawe_taylor_prime({bits})=None passed");
+        } else {
+            println!("shawe_taylor_prime({bits})={result:?} error: expected None");
+        }
+    }
+}
+
+pub fn test_random_provable_prime() {
+    let cases = vec![2, 40, 128, 512];
+    let mut rng = SplitMix64::new(46);
+
+    for bits in cases {
+        let (seed, prime) = random_provable_prime(bits, &mut rng);
+        let result = prime.p.bits() == bits
+            && verify_certificate(&prime.certificate)
+            && baillie_psw(&prime.p)
+            && shawe_taylor_prime(bits, &seed).as_ref() == Some(&prime);
+        if result {
+            println!("random_provable_prime({bits})={} passed", prime.p);
+        } else {
+            println!("random_provable_prime({bits})={prime:?} error: not replayable");
+        }
+    }
+}
+
+pub fn test_mersenne_primes() {
+    let cases = vec![
+        (2, vec![2]),
+        (20, vec![2, 3, 5, 7, 13, 17, 19]),
+        (
+            1300,
+            vec![2, 3, 5, 7, 13, 17, 19, 31, 61, 89, 107, 127, 521, 607, 1279],
+        ),
+    ];
+
+    for ele in cases {
+        let result = mersenne_primes(ele.0);
+        if result == ele.1 {
+            println!("mersenne_primes({})={:?} passed", ele.0, result);
+        } else {
+            println!(
+                "mersenne_primes({})={:?} error: expected {:?}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_proth_primes() {
+    let cases = vec![
+        ((1, 40), vec![1, 2, 4, 8, 16]),
+        (
+            (3, 250),
+            vec![2, 5, 6, 8, 12, 18, 30, 36, 41, 66, 189, 201, 209],
+        ),
+        ((5, 250), vec![3, 7, 13, 15, 25, 39, 55, 75, 85, 127]),
+    ];
+
+    for ele in cases {
+        let result = proth_primes(ele.0.0, ele.0.1);
+        let exponents: Vec<usize> = result.iter().map(|x| x.0).collect();
+        let values = result
+            .iter()
+            .all(|(n, p)| *p == (Scalable::from(ele.0.0) << *n) + 1 && baillie_psw(p));
+        if exponents == ele.1 && values {
+            println!(
+                "proth_primes({},{})={:?} passed",
+                ele.0.0, ele.0.1, exponents
+            );
+        } else {
+            println!(
+                "proth_primes({},{})={:?} error: expected {:?}",
+                ele.0.0, ele.0.1, exponents, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_fermat_primes() {
+    let exp = vec![(0, 3), (1, 5), (2, 17), (3, 257), (4, 65537)];
+    let result: Vec<(u32, Option<i64>)> = fermat_primes(10)
+        .into_iter()
+        .map(|(n, p)| (n, p.to_i64()))
+        .collect();
+    let exp: Vec<(u32, Option<i64>)> = exp.into_iter().map(|(n, p)| (n, Some(p))).collect();
+    if result == exp {
+        println!("fermat_primes(10)={:?} passed", result);
+    } else {
+        println!("fermat_primes(10)={:?} error: expected {:?}", result, exp);
+    }
+}
+
+pub fn test_generate_primes() {
+    test_random_prime();
+    println!();
+    test_parallel_random_prime();
+    println!();
+    test_random_safe_prime();
+    println!();
+    test_strong_prime();
+    println!();
+    test_check_strong_prime();
+    println!();
+    test_certified_prime();
+    println!();
+    test_shawe_taylor_prime();
+    println!();
+    test_random_provable_prime();
+    println!();
+    test_mersenne_primes();
+    println!();
+    test_proth_primes();
+    println!();
+    test_fermat_primes();
+    println!();
+}
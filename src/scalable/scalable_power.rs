@@ -1 +1,295 @@
+use super::scalables::{Scalable, modulo};
 
+/* Naive power function. Linear complexity
+   @param x base
+   @param n exponent
+*/
+pub fn pow(x: &Scalable, n: u64) -> Scalable {
+    let mut result = Scalable::one();
+    for _ in 0..n {
+        result = &result * x;
+    }
+    result
+}
+
+/* Fast integer exponentiation function. Logarithmic complexity.
+   @param x base
+   @param n exponent
+*/
+pub fn power(x: &Scalable, n: u64) -> Scalable {
+    let mut result = Scalable::one();
+    for i in (0..u64::BITS - n.leading_zeros()).rev() {
+        result = &result * &result;
+        if (n >> i) & 1 == 1 {
+            result = &result * x;
+        }
+    }
+    result
+}
+
+/* Fast modular exponentiation function. Logarithmic complexity.
+   Square and multiply scanning bits of exponent from the most
+   significant one.
+   @param x base
+   @param n natural exponent
+   @param m modular base
+*/
+pub fn mod_power(x: &Scalable, n: &Scalable, m: &Scalable) -> Scalable {
+    let x = modulo(x, m);
+    let mut result = modulo(&Scalable::one(), m);
+    for i in (0..n.bits()).rev() {
+        result = modulo(&(&result * &result), m);
+        if n.bit(i) {
+            result = modulo(&(&result * &x), m);
+        }
+    }
+    result
+}
+
+/* Fast modular exponentiation function mod prime. Logarithmic complexity.
+   It makes use of the Little Fermat Theorem: exponent is first reduced
+   modulo p - 1 when x is not a multiple of p.
+   @param x base
+   @param n natural exponent
+   @param p prime modular base
+*/
+pub fn prime_mod_power(x: &Scalable, n: &Scalable, p: &Scalable) -> Scalable {
+    if modulo(x, p).is_zero() {
+        return if n.is_zero() {
+            Scalable::one()
+        } else {
+            Scalable::zero()
+        };
+    }
+    mod_power(x, &modulo(n, &(p - 1)), p)
+}
+
+/* Integer square root, greatest r such that r * r <= x. Newton's
+   iteration starting above the root. Logarithmic number of steps.
+   @param x non negative integer
+*/
+pub fn isqrt(x: &Scalable) -> Scalable {
+    if x.is_negative() {
+        panic!("x must be >= 0");
+    }
+    if x.is_zero() {
+        return Scalable::zero();
+    }
+    let mut r = Scalable::pow2(x.bits().div_ceil(2));
+    loop {
+        let next = (&r + &(x / &r)) >> 1;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
+
+/* Integer k-th root, greatest r such that r^k <= x. Newton's iteration
+   starting above the root.
+   @param x non negative integer
+   @param k root index, at least 1.
+*/
+pub fn iroot(x: &Scalable, k: u64) -> Scalable {
+    if x.is_negative() || k == 0 {
+        panic!("x must be >= 0 and k > 0");
+    }
+    if x.is_zero() || k == 1 {
+        return x.clone();
+    }
+    let mut r = Scalable::pow2(x.bits().div_ceil(k as usize));
+    loop {
+        let next = (&r * (k as i64 - 1) + &(x / &power(&r, k - 1))) / k as i64;
+        if next >= r {
+            return r;
+        }
+        r = next;
+    }
+}
+
+// ========================= TESTING =========================
+
+fn s(x: &str) -> Scalable {
+    Scalable::from_str_radix(x, 10).unwrap()
+}
+
+pub fn test_pow() {
+    let cases = vec![
+        (("-1", 12), "1"),
+        (("-1", 11), "-1"),
+        (("0", 2), "0"),
+        (("3", 1), "3"),
+        (("5", 0), "1"),
+        (("-2", 3), "-8"),
+        (("2", 100), "1267650600228229401496703205376"),
+    ];
+
+    for ele in cases {
+        let result = pow(&s(ele.0.0), ele.0.1);
+        if result == s(ele.1) {
+            println!("pow({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "pow({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_power() {
+    let cases = vec![
+        (("-1", 12), "1"),
+        (("-1", 11), "-1"),
+        (("0", 2), "0"),
+        (("3", 1), "3"),
+        (("5", 0), "1"),
+        (("-2", 3), "-8"),
+        (("3", 50), "717897987691852588770249"),
+    ];
+
+    for ele in cases {
+        let result = power(&s(ele.0.0), ele.0.1);
+        if result == s(ele.1) {
+            println!("power({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "power({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_mod_power() {
+    let cases = vec![
+        (("-1", "12", "10"), "1"),
+        (("-1", "11", "11"), "10"),
+        (("0", "2", "3"), "0"),
+        (("3", "1", "3"), "0"),
+        (("5", "0", "2"), "1"),
+        (("-2", "2", "5"), "4"),
+        (("-2", "3", "9"), "1"),
+        (("2", "5", "17"), "15"),
+        (
+            (
+                "2",
+                "170141183460469231731687303715884105726",
+                "170141183460469231731687303715884105727",
+            ),
+            "1",
+        ),
+        (("3", "1000000000000000000000", "1000000007"), "526304509"),
+    ];
+
+    for ele in cases {
+        let result = mod_power(&s(ele.0.0), &s(ele.0.1), &s(ele.0.2));
+        if result == s(ele.1) {
+            println!(
+                "mod_power({},{},{})={} passed",
+                ele.0.0, ele.0.1, ele.0.2, result
+            );
+        } else {
+            println!(
+                "mod_power({},{},{})={} error: expected {}",
+                ele.0.0, ele.0.1, ele.0.2, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_prime_mod_power() {
+    let cases = vec![
+        (("-1", "12", "7"), "1"),
+        (("-1", "11", "11"), "10"),
+        (("0", "2", "3"), "0"),
+        (("3", "1", "3"), "0"),
+        (("5", "0", "2"), "1"),
+        (("-2", "3", "5"), "2"),
+        (("3", "1000000000000000000000", "1000000007"), "526304509"),
+    ];
+
+    for ele in cases {
+        let result = prime_mod_power(&s(ele.0.0), &s(ele.0.1), &s(ele.0.2));
+        if result == s(ele.1) {
+            println!(
+                "prime_mod_power({},{},{})={} passed",
+                ele.0.0, ele.0.1, ele.0.2, result
+            );
+        } else {
+            println!(
+                "prime_mod_power({},{},{})={} error: expected {}",
+                ele.0.0, ele.0.1, ele.0.2, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_isqrt() {
+    let cases = vec![
+        ("0", "0"),
+        ("1", "1"),
+        ("3", "1"),
+        ("4", "2"),
+        ("99", "9"),
+        ("18446744073709551616", "4294967296"),
+        (
+            "28948022309329048855892746252171976963317496166410141009864396001978282409983",
+            "170141183460469231731687303715884105727",
+        ),
+        (
+            "28948022309329048855892746252171976963317496166410141009864396001978282409984",
+            "170141183460469231731687303715884105728",
+        ),
+    ];
+
+    for ele in cases {
+        let result = isqrt(&s(ele.0));
+        if result == s(ele.1) {
+            println!("isqrt({})={} passed", ele.0, result);
+        } else {
+            println!("isqrt({})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_iroot() {
+    let cases = vec![
+        (("0", 3), "0"),
+        (("7", 1), "7"),
+        (("26", 3), "2"),
+        (("27", 3), "3"),
+        (("1000000", 6), "10"),
+        (("999999", 6), "9"),
+        (("1267650600228229401496703205376", 100), "2"),
+        (("1000005000010000010000005000000", 5), "1000000"),
+        (("1000005000010000010000005000001", 5), "1000001"),
+    ];
+
+    for ele in cases {
+        let result = iroot(&s(ele.0.0), ele.0.1);
+        if result == s(ele.1) {
+            println!("iroot({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "iroot({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_powers() {
+    test_pow();
+    println!();
+    test_power();
+    println!();
+    test_mod_power();
+    println!();
+    test_prime_mod_power();
+    println!();
+    test_isqrt();
+    println!();
+    test_iroot();
+    println!();
+}
@@ -1 +1,989 @@
+use std::time::{Duration, Instant};
 
+use super::{
+    scalable_basic_arithmetics::{gcd, jacobi},
+    scalable_power::{iroot, isqrt, mod_power, power},
+    scalables::{Scalable, modulo},
+};
+use crate::builtin::generate_primes::eratosthenes;
+use crate::rng::{Rng, SplitMix64, uniform_scalable};
+
+/* Deterministic primality test by trial division.
+   Only practical for small integers.
+*/
+pub fn is_prime(n: &Scalable) -> bool {
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    let mut i = Scalable::from(2i64);
+    while &i * &i <= *n {
+        if modulo(n, &i).is_zero() {
+            return false;
+        }
+        i = i + 1;
+    }
+    true
+}
+
+/* Primality test based on small Fermat theorem
+   @param p tested integer
+   @param test_seq sequence of integers against which to test
+*/
+pub fn is_pseudo_prime(p: &Scalable, test_seq: &[Scalable]) -> bool {
+    for elt in test_seq {
+        if mod_power(elt, p, p) != modulo(elt, p) {
+            return false;
+        }
+    }
+    true
+}
+
+/* Primality test based on Euler's criterion: for prime p and a coprime
+   to p, a^((p - 1) / 2) = (a/p) mod p. Unlike Fermat's test no composite
+   passes it for every base, Carmichael numbers included. Bases multiple
+   of p tell nothing and are skipped, as `is_pseudo_prime` lets them
+   pass, while any other base sharing a factor with p proves it composite.
+   @param p odd integer greater than 2.
+   @param test_seq sequence of integers against which to test
+*/
+pub fn is_euler_pseudo_prime(p: &Scalable, test_seq: &[Scalable]) -> bool {
+    let half = (p - 1) >> 1;
+    for elt in test_seq {
+        if modulo(elt, p).is_zero() {
+            continue;
+        }
+        let j = jacobi(elt, p);
+        if j == 0 || mod_power(elt, &half, p) != modulo(&Scalable::from(j), p) {
+            return false;
+        }
+    }
+    true
+}
+
+/* Solovay-Strassen primality test with random bases in [2, n - 1]. A
+   composite passes a round with probability at most 1/2, so at most
+   2^-rounds overall.
+   @param n tested integer
+   @param rounds number of random bases.
+   @param rng random generator drawing the bases.
+*/
+pub fn solovay_strassen(n: &Scalable, rounds: usize, rng: &mut impl Rng) -> bool {
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    if *n == 2 {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    let range = n - 2;
+    (0..rounds).all(|_| is_euler_pseudo_prime(n, &[uniform_scalable(&range, rng) + 2]))
+}
+
+/* Strong probable prime test to a single base: writing n - 1 = d * 2^s
+   with d odd, n passes when a^d = 1 or a^(d * 2^r) = -1 mod n for some
+   r < s.
+   @param n odd integer greater than 2.
+   @param a base.
+*/
+pub fn is_strong_pseudo_prime(n: &Scalable, a: &Scalable) -> bool {
+    let n_minus_one = n - 1;
+    let s = n_minus_one.trailing_zeros();
+    let d = &n_minus_one >> s;
+    let mut x = mod_power(a, &d, n);
+    if x.is_one() || x == n_minus_one || modulo(a, n).is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        x = modulo(&(&x * &x), n);
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+/* Miller-Rabin primality test with random bases in [2, n - 2]. A
+   composite passes a round with probability at most 1/4, so at most
+   4^-rounds overall.
+   @param n tested integer
+   @param rounds number of random bases.
+   @param rng random generator drawing the bases.
+*/
+pub fn miller_rabin(n: &Scalable, rounds: usize, rng: &mut impl Rng) -> bool {
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    if n.bits() == 2 {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    let range = n - 3;
+    (0..rounds).all(|_| is_strong_pseudo_prime(n, &(uniform_scalable(&range, rng) + 2)))
+}
+
+/* Lucas sequences U_k(P, Q) and V_k(P, Q) modulo n, together with Q^k
+   mod n, by doubling and adding along the bits of k. With D = P^2 - 4Q:
+   U_2k = U_k V_k, V_2k = V_k^2 - 2Q^k, U_k+1 = (P U_k + V_k) / 2 and
+   V_k+1 = (D U_k + P V_k) / 2.
+   @param p parameter P.
+   @param q parameter Q.
+   @param k non negative index.
+   @param n odd modular base.
+*/
+pub fn lucas_sequence(
+    p: &Scalable,
+    q: &Scalable,
+    k: &Scalable,
+    n: &Scalable,
+) -> (Scalable, Scalable, Scalable) {
+    let (p, q) = (modulo(p, n), modulo(q, n));
+    let d = modulo(&(&p * &p - &q * 4), n);
+    let half = |x: Scalable| if x.is_even() { x >> 1 } else { (x + n) >> 1 };
+    let mut u = Scalable::zero();
+    let mut v = modulo(&Scalable::from(2i64), n);
+    let mut qk = modulo(&Scalable::one(), n);
+    for i in (0..k.bits()).rev() {
+        u = modulo(&(&u * &v), n);
+        v = modulo(&(&v * &v - &qk * 2), n);
+        qk = modulo(&(&qk * &qk), n);
+        if k.bit(i) {
+            let next_u = half(modulo(&(&p * &u + &v), n));
+            v = half(modulo(&(&d * &u + &p * &v), n));
+            u = next_u;
+            qk = modulo(&(&qk * &q), n);
+        }
+    }
+    (u, v, qk)
+}
+
+/* Selfridge's parameters for the strong Lucas test: first D in
+   5, -7, 9, -11, ... such that (D/n) = -1, with P = 1 and
+   Q = (1 - D) / 4. None when some D reveals a factor of n.
+   @param n odd integer, not a perfect square.
+*/
+fn selfridge_parameters(n: &Scalable) -> Option<(i64, i64, i64)> {
+    let mut d: i64 = 5;
+    loop {
+        match jacobi(&Scalable::from(d), n) {
+            -1 => return Some((d, 1, (1 - d) / 4)),
+            0 if *n != d.abs() => return None,
+            _ => d = if d > 0 { -d - 2 } else { -d + 2 },
+        }
+    }
+}
+
+/* Strong Lucas probable prime test with Selfridge's parameters: writing
+   n + 1 = d * 2^s with d odd, n passes when U_d = 0 or V_(d * 2^r) = 0
+   mod n for some r < s.
+   @param n odd integer greater than 2.
+*/
+pub fn is_strong_lucas_pseudo_prime(n: &Scalable) -> bool {
+    let root = isqrt(n);
+    if &root * &root == *n {
+        return false;
+    }
+    let Some((_, p, q)) = selfridge_parameters(n) else {
+        return false;
+    };
+    let n_plus_one = n + 1;
+    let s = n_plus_one.trailing_zeros();
+    let d = &n_plus_one >> s;
+    let (u, mut v, mut qk) = lucas_sequence(&Scalable::from(p), &Scalable::from(q), &d, n);
+    if u.is_zero() || v.is_zero() {
+        return true;
+    }
+    for _ in 1..s {
+        v = modulo(&(&v * &v - &qk * 2), n);
+        qk = modulo(&(&qk * &qk), n);
+        if v.is_zero() {
+            return true;
+        }
+    }
+    false
+}
+
+/* Baillie-PSW primality test: strong probable prime to base 2 and
+   strong Lucas probable prime. No composite passing both is known.
+   @param n tested integer
+*/
+pub fn baillie_psw(n: &Scalable) -> bool {
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    if *n == 2 {
+        return true;
+    }
+    if n.is_even() {
+        return false;
+    }
+    is_strong_pseudo_prime(n, &Scalable::from(2i64)) && is_strong_lucas_pseudo_prime(n)
+}
+
+/* Reduction modulo the Mersenne number 2^p - 1 without division: since
+   2^p = 1, x = (x mod 2^p) + (x >> p) until x fits in p bits.
+   @param x non negative integer
+   @param p exponent of the Mersenne number.
+*/
+fn mersenne_modulo(x: &Scalable, p: usize) -> Scalable {
+    let mut x = x.clone();
+    while x.bits() > p {
+        x = x.bit_slice(0, p) + (x >> p);
+    }
+    // x + 1 = 2^p only when x = 2^p - 1 = 0
+    if (&x + 1).bits() > p {
+        return Scalable::zero();
+    }
+    x
+}
+
+/* Lucas-Lehmer test: for an odd prime p, M = 2^p - 1 is prime if and
+   only if s_(p - 2) = 0 mod M, where s_0 = 4 and s_(i + 1) = s_i^2 - 2.
+   False for p < 2, M being 0 or 1.
+   @param p prime exponent.
+*/
+pub fn lucas_lehmer(p: u64) -> bool {
+    if p < 2 {
+        return false;
+    }
+    if p == 2 {
+        return true;
+    }
+    let p = p as usize;
+    let m = Scalable::pow2(p) - 1;
+    let mut s = Scalable::from(4i64);
+    for _ in 0..p - 2 {
+        s = mersenne_modulo(&(&s * &s + &m - 2), p);
+    }
+    s.is_zero()
+}
+
+/* Proth's theorem test for N = k * 2^n + 1 with 0 < k < 2^n: N is
+   prime if and only if a^((N - 1) / 2) = -1 mod N for some a. Any a
+   with (a/N) = -1 decides, the first such odd a is used.
+   @param k multiplier, 0 < k < 2^n.
+   @param n exponent of 2.
+*/
+pub fn proth(k: &Scalable, n: usize) -> bool {
+    if k.is_negative() || k.is_zero() || k.bits() > n {
+        panic!("k must satisfy 0 < k < 2^n");
+    }
+    let big_n = (k << n) + 1;
+    let root = isqrt(&big_n);
+    if &root * &root == big_n {
+        return false;
+    }
+    let n_minus_one = &big_n - 1;
+    let mut a = Scalable::from(3i64);
+    loop {
+        match jacobi(&a, &big_n) {
+            -1 => return mod_power(&a, &(&n_minus_one >> 1), &big_n) == n_minus_one,
+            0 if a != big_n => return false,
+            _ => a = a + 2,
+        }
+    }
+}
+
+/* Largest index accepted by `pepin`. F_24 already has 2^24 bits and
+   is the largest Fermat number ever settled by Pepin's test.
+*/
+pub const PEPIN_MAX_INDEX: u32 = 24;
+
+/* Pepin's test for the Fermat number F_n = 2^(2^n) + 1: for n > 0, F_n
+   is prime if and only if 3^((F_n - 1) / 2) = -1 mod F_n.
+   @param n index of the Fermat number, at most PEPIN_MAX_INDEX.
+*/
+pub fn pepin(n: u32) -> bool {
+    if n > PEPIN_MAX_INDEX {
+        panic!("n must be <= {PEPIN_MAX_INDEX}");
+    }
+    if n == 0 {
+        return true;
+    }
+    let f = Scalable::pow2(1usize << n) + 1;
+    let n_minus_one = &f - 1;
+    mod_power(&Scalable::from(3i64), &(&n_minus_one >> 1), &f) == n_minus_one
+}
+
+/* Polynomials modulo (X^r - 1, n), as r coefficients in [0, n[.
+   Products go through Kronecker substitution: both polynomials are
+   packed into one integer with a slot of bits per coefficient wide
+   enough for any coefficient of the product, multiplied once, then
+   unpacked and folded with X^r = 1.
+*/
+struct PolynomialRing {
+    r: usize,
+    n: Scalable,
+    slot: usize,
+}
+
+impl PolynomialRing {
+    fn new(r: usize, n: &Scalable) -> PolynomialRing {
+        let bound = Scalable::from(r as u64) * &(n * n);
+        PolynomialRing {
+            r,
+            n: n.clone(),
+            slot: bound.bits() + 1,
+        }
+    }
+
+    fn pack(&self, p: &[Scalable]) -> Scalable {
+        p.iter()
+            .rev()
+            .fold(Scalable::zero(), |acc, c| (acc << self.slot) + c)
+    }
+
+    fn mul(&self, a: &[Scalable], b: &[Scalable]) -> Vec<Scalable> {
+        let packed = &self.pack(a) * &self.pack(b);
+        let mut result = vec![Scalable::zero(); self.r];
+        for i in 0..packed.bits().div_ceil(self.slot) {
+            result[i % self.r] = &result[i % self.r] + &packed.bit_slice(i * self.slot, self.slot);
+        }
+        result.iter().map(|c| modulo(c, &self.n)).collect()
+    }
+
+    /* (X + a)^e by square and multiply. */
+    fn power_linear(&self, a: &Scalable, e: &Scalable) -> Vec<Scalable> {
+        let mut base = vec![Scalable::zero(); self.r];
+        base[0] = modulo(a, &self.n);
+        base[1 % self.r] = &base[1 % self.r] + 1;
+        let mut result = vec![Scalable::zero(); self.r];
+        result[0] = Scalable::one();
+        for i in (0..e.bits()).rev() {
+            result = self.mul(&result, &result);
+            if e.bit(i) {
+                result = self.mul(&result, &base);
+            }
+        }
+        result
+    }
+}
+
+/* Multiplicative order of n modulo r exceeds bound. */
+fn order_exceeds(n: u64, r: u64, bound: u64) -> bool {
+    let mut x = 1;
+    for _ in 0..bound {
+        x = (x as u128 * n as u128 % r as u128) as u64;
+        if x == 1 {
+            return false;
+        }
+    }
+    true
+}
+
+fn euler_phi(mut r: u64) -> u64 {
+    let mut result = r;
+    let mut q = 2;
+    while q * q <= r {
+        if r.is_multiple_of(q) {
+            while r.is_multiple_of(q) {
+                r /= q;
+            }
+            result -= result / q;
+        }
+        q += 1;
+    }
+    if r > 1 {
+        result -= result / r;
+    }
+    result
+}
+
+/* AKS deterministic polynomial time primality test (Agrawal, Kayal,
+   Saxena). With l the bit size of n, standing for log2(n):
+   1. n = a^b with b > 1 is composite,
+   2. r is the least integer such that the order of n mod r exceeds l^2,
+   3. n is composite if it shares a non trivial factor with some a <= r,
+   4. n <= r is then prime,
+   5. n is composite if (X + a)^n != X^n + a mod (X^r - 1, n) for some
+      a <= sqrt(phi(r)) * l,
+   6. otherwise n is prime.
+   Polynomial but far slower than Miller-Rabin, for comparison only.
+   @param n tested integer
+*/
+pub fn aks(n: &Scalable) -> bool {
+    if n.is_negative() || n.bits() < 2 {
+        return false;
+    }
+    let l = n.bits() as u64;
+    for b in 2..=l {
+        let a = iroot(n, b);
+        if a.bits() < 2 {
+            break;
+        }
+        if power(&a, b) == *n {
+            return false;
+        }
+    }
+
+    let mut r = 2;
+    loop {
+        let n_mod_r = n.rem_u64(r);
+        if gcd(&Scalable::from(r), n).is_one() && order_exceeds(n_mod_r, r, l * l) {
+            break;
+        }
+        r += 1;
+    }
+
+    for a in 2..=r {
+        let a = Scalable::from(a);
+        if &a >= n {
+            break;
+        }
+        let d = gcd(&a, n);
+        if !d.is_one() {
+            return false;
+        }
+    }
+    if *n <= Scalable::from(r) {
+        return true;
+    }
+
+    let ring = PolynomialRing::new(r as usize, n);
+    let limit = (isqrt(&Scalable::from(euler_phi(r))) + 1).to_u64().unwrap() * l;
+    let shift = n.rem_u64(r) as usize;
+    for a in 1..=limit {
+        let a = Scalable::from(a);
+        let mut expected = vec![Scalable::zero(); r as usize];
+        expected[0] = modulo(&a, n);
+        expected[shift] = modulo(&(&expected[shift] + 1), n);
+        if ring.power_linear(&a, n) != expected {
+            return false;
+        }
+    }
+    true
+}
+
+/* Outcome and running time of a primality test on one input. */
+#[derive(Debug, Clone)]
+pub struct PrimalityTiming {
+    pub test: &'static str,
+    pub is_prime: bool,
+    pub elapsed: Duration,
+}
+
+/* Run trial division, Miller-Rabin with 20 rounds, Baillie-PSW and AKS
+   on the same input and time each of them.
+   @param n tested integer
+   @param rng random generator drawing Miller-Rabin bases.
+*/
+pub fn compare_primality_tests(n: &Scalable, rng: &mut impl Rng) -> Vec<PrimalityTiming> {
+    let mut result = vec![];
+    let mut time = |test: &'static str, f: &mut dyn FnMut() -> bool| {
+        let start = Instant::now();
+        let is_prime = f();
+        result.push(PrimalityTiming {
+            test,
+            is_prime,
+            elapsed: start.elapsed(),
+        });
+    };
+    time("trial division", &mut || is_prime(n));
+    time("miller-rabin", &mut || miller_rabin(n, 20, rng));
+    time("baillie-psw", &mut || baillie_psw(n));
+    time("aks", &mut || aks(n));
+    result
+}
+
+// ========================= TESTING =========================
+
+fn s(x: &str) -> Scalable {
+    Scalable::from_str_radix(x, 10).unwrap()
+}
+
+pub fn test_is_prime() {
+    let cases = vec![
+        ("2", true),
+        ("3", true),
+        ("13", true),
+        ("4", false),
+        ("77", false),
+        ("1", false),
+        ("1000000007", true),
+        ("1000000011", false),
+    ];
+
+    for ele in cases {
+        let result = is_prime(&s(ele.0));
+        if result == ele.1 {
+            println!("is_prime({})={} passed", ele.0, result);
+        } else {
+            println!("is_prime({})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_is_pseudo_prime() {
+    let cases = vec![
+        (("11", vec!["2", "4", "5", "20"]), true),
+        (("29", vec!["30", "41", "52"]), true),
+        (("15", vec!["2", "9", "15", "18"]), false),
+        (
+            (
+                "170141183460469231731687303715884105727",
+                vec!["2", "3", "5", "7"],
+            ),
+            true,
+        ),
+        (
+            (
+                "170141183460469231731687303715884105729",
+                vec!["2", "3", "5", "7"],
+            ),
+            false,
+        ),
+    ];
+
+    for ele in cases {
+        let seq: Vec<Scalable> = ele.0.1.iter().map(|x| s(x)).collect();
+        let result = is_pseudo_prime(&s(ele.0.0), &seq);
+        if result == ele.1 {
+            println!(
+                "is_pseudo_prime({},{:?})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "is_pseudo_prime({},{:?})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_is_euler_pseudo_prime() {
+    let cases = vec![
+        (("11", vec!["2", "4", "5", "10"]), true),
+        (("561", vec!["2"]), true),
+        (("1729", vec!["2", "5", "23"]), true),
+        (("15", vec!["2"]), false),
+        (("11", vec!["22", "-11", "2"]), true),
+        (("21", vec!["42", "7"]), false),
+        (
+            (
+                "170141183460469231731687303715884105727",
+                vec!["2", "3", "5", "7"],
+            ),
+            true,
+        ),
+    ];
+
+    for ele in cases {
+        let seq: Vec<Scalable> = ele.0.1.iter().map(|x| s(x)).collect();
+        let result = is_euler_pseudo_prime(&s(ele.0.0), &seq);
+        if result == ele.1 {
+            println!(
+                "is_euler_pseudo_prime({},{:?})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "is_euler_pseudo_prime({},{:?})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+/* Carmichael numbers pass Fermat's test to every base coprime to them
+   but not Euler's. Bases sharing a factor with n are left out, so that
+   failures come from Euler's criterion alone. The largest one is
+   Chernick's (6k + 1)(12k + 1)(18k + 1).
+*/
+pub fn test_solovay_strassen() {
+    let mut rng = SplitMix64::new(38);
+
+    for n in [
+        "561",
+        "1729",
+        "41041",
+        "825265",
+        "1296000000000000000000641948400000000000000105992219160000000000005833464973129",
+    ] {
+        let bases: Vec<Scalable> = eratosthenes(37)
+            .into_iter()
+            .map(Scalable::from)
+            .filter(|a| gcd(a, &s(n)).is_one())
+            .collect();
+        let fermat = is_pseudo_prime(&s(n), &bases);
+        let euler = is_euler_pseudo_prime(&s(n), &bases);
+        let result = solovay_strassen(&s(n), 20, &mut rng);
+        if fermat && !euler && !result {
+            println!("solovay_strassen({n})={result} (fermat {fermat}, euler {euler}) passed");
+        } else {
+            println!(
+                "solovay_strassen({n})={result} (fermat {fermat}, euler {euler}) error: \
+                 expected false (fermat true, euler false)"
+            );
+        }
+    }
+
+    for (n, exp) in [
+        (s("2"), true),
+        (s("3"), true),
+        (s("1000000007"), true),
+        (Scalable::pow2(521) - 1, true),
+        (Scalable::pow2(521) + 1, false),
+    ] {
+        let result = solovay_strassen(&n, 20, &mut rng);
+        if result == exp {
+            println!("solovay_strassen({n})={result} passed");
+        } else {
+            println!("solovay_strassen({n})={result} error: expected {exp}");
+        }
+    }
+}
+
+pub fn test_is_strong_pseudo_prime() {
+    let cases = vec![
+        (("2047", "2"), true),
+        (("2047", "3"), false),
+        (("561", "2"), false),
+        (("3825123056546413051", "31"), true),
+        (("3825123056546413051", "37"), false),
+        (("170141183460469231731687303715884105727", "3"), true),
+    ];
+
+    for ele in cases {
+        let result = is_strong_pseudo_prime(&s(ele.0.0), &s(ele.0.1));
+        if result == ele.1 {
+            println!(
+                "is_strong_pseudo_prime({},{})={} passed",
+                ele.0.0, ele.0.1, result
+            );
+        } else {
+            println!(
+                "is_strong_pseudo_prime({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_miller_rabin() {
+    let mut cases: Vec<(Scalable, bool)> = vec![
+        ("1", false),
+        ("2", true),
+        ("3", true),
+        ("4", false),
+        ("5", true),
+        ("561", false),
+        ("41041", false),
+        ("1000000007", true),
+        ("3825123056546413051", false),
+        ("170141183460469231731687303715884105727", true),
+        ("170141183460469231731687303715884105729", false),
+    ]
+    .into_iter()
+    .map(|(x, b)| (s(x), b))
+    .collect();
+    cases.push((
+        s("340282366920938463463374607431768211507") * s("18446744073709551629"),
+        false,
+    ));
+    cases.push((Scalable::pow2(521) - 1, true));
+    cases.push((Scalable::pow2(521) + 1, false));
+    let mut rng = SplitMix64::new(36);
+
+    for ele in cases {
+        let result = miller_rabin(&ele.0, 20, &mut rng);
+        if result == ele.1 {
+            println!("miller_rabin({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "miller_rabin({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+
+    let seq: Vec<Scalable> = ["2", "5", "7"].iter().map(|x| s(x)).collect();
+    let fooled = is_pseudo_prime(&s("561"), &seq);
+    if fooled && !miller_rabin(&s("561"), 20, &mut rng) {
+        println!("561 fools is_pseudo_prime but not miller_rabin passed");
+    } else {
+        println!("561 fools is_pseudo_prime but not miller_rabin error");
+    }
+}
+
+pub fn test_lucas_sequence() {
+    let cases = vec![
+        (("1", "-1", "10", "1000"), ("55", "123", "1")),
+        (
+            ("1", "-1", "90", "1000000007"),
+            ("210345902", "300062631", "1"),
+        ),
+        (
+            ("3", "2", "200", "170141183460469231731687303715884105727"),
+            (
+                "9444732965739290427391",
+                "9444732965739290427393",
+                "9444732965739290427392",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let (p, q, k, n) = ele.0;
+        let result = lucas_sequence(&s(p), &s(q), &s(k), &s(n));
+        let exp = (s(ele.1.0), s(ele.1.1), s(ele.1.2));
+        if result == exp {
+            println!(
+                "lucas_sequence({p},{q},{k},{n})=({},{},{}) passed",
+                result.0, result.1, result.2
+            );
+        } else {
+            println!(
+                "lucas_sequence({p},{q},{k},{n})=({},{},{}) error: expected {:?}",
+                result.0, result.1, result.2, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_is_strong_lucas_pseudo_prime() {
+    let cases = vec![
+        ("5459", true),
+        ("5777", true),
+        ("18971", true),
+        ("2047", false),
+        ("121", false),
+        ("170141183460469231731687303715884105727", true),
+        ("170141183460469231731687303715884105729", false),
+    ];
+
+    for ele in cases {
+        let result = is_strong_lucas_pseudo_prime(&s(ele.0));
+        if result == ele.1 {
+            println!("is_strong_lucas_pseudo_prime({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "is_strong_lucas_pseudo_prime({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_baillie_psw() {
+    let mut cases: Vec<(Scalable, bool)> = vec![
+        ("1", false),
+        ("2", true),
+        ("3", true),
+        ("561", false),
+        ("2047", false),
+        ("5459", false),
+        ("3825123056546413051", false),
+        ("170141183460469231731687303715884105727", true),
+    ]
+    .into_iter()
+    .map(|(x, b)| (s(x), b))
+    .collect();
+    cases.push((
+        s("340282366920938463463374607431768211507") * s("18446744073709551629"),
+        false,
+    ));
+    cases.push((Scalable::pow2(521) - 1, true));
+    cases.push((Scalable::pow2(521) + 1, false));
+    let square = s("340282366920938463463374607431768211507");
+    cases.push((&square * &square, false));
+
+    for ele in cases {
+        let result = baillie_psw(&ele.0);
+        if result == ele.1 {
+            println!("baillie_psw({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "baillie_psw({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+
+    let result =
+        (2..5000i64).all(|n| baillie_psw(&Scalable::from(n)) == is_prime(&Scalable::from(n)));
+    if result {
+        println!("baillie_psw agrees with is_prime below 5000 passed");
+    } else {
+        println!("baillie_psw agrees with is_prime below 5000 error");
+    }
+}
+
+pub fn test_lucas_lehmer() {
+    let cases = vec![
+        (0, false),
+        (1, false),
+        (2, true),
+        (3, true),
+        (7, true),
+        (11, false),
+        (23, false),
+        (31, true),
+        (61, true),
+        (67, false),
+        (127, true),
+        (521, true),
+        (523, false),
+    ];
+
+    for ele in cases {
+        let result = lucas_lehmer(ele.0);
+        if result == ele.1 {
+            println!("lucas_lehmer({})={} passed", ele.0, result);
+        } else {
+            println!(
+                "lucas_lehmer({})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+
+    let mut rng = SplitMix64::new(41);
+    let result = [13, 17, 19, 29, 37, 41, 43, 47, 53, 59, 89, 97]
+        .iter()
+        .all(|p| {
+            lucas_lehmer(*p) == miller_rabin(&(Scalable::pow2(*p as usize) - 1), 20, &mut rng)
+        });
+    if result {
+        println!("lucas_lehmer agrees with miller_rabin passed");
+    } else {
+        println!("lucas_lehmer agrees with miller_rabin error");
+    }
+}
+
+pub fn test_proth() {
+    let cases = vec![
+        (("1", 1), true),
+        (("1", 16), true),
+        (("1", 32), false),
+        (("3", 2), true),
+        (("3", 3), false),
+        (("3", 41), true),
+        (("5", 127), true),
+        (("5", 129), false),
+        (("13", 4), false),
+        (("15", 4), true),
+    ];
+
+    for ele in cases {
+        let result = proth(&s(ele.0.0), ele.0.1);
+        if result == ele.1 {
+            println!("proth({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "proth({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_pepin() {
+    let cases = vec![
+        (0, true),
+        (1, true),
+        (4, true),
+        (5, false),
+        (6, false),
+        (11, false),
+    ];
+
+    for ele in cases {
+        let result = pepin(ele.0);
+        if result == ele.1 {
+            println!("pepin({})={} passed", ele.0, result);
+        } else {
+            println!("pepin({})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_aks() {
+    let cases = vec![
+        ("1", false),
+        ("2", true),
+        ("3", true),
+        ("4", false),
+        ("31", true),
+        ("97", true),
+        ("121", false),
+        ("561", false),
+        ("1729", false),
+        ("7919", true),
+        ("16129", false),
+        ("65537", true),
+        ("1022117", false),
+    ];
+
+    for ele in cases {
+        let result = aks(&s(ele.0));
+        if result == ele.1 {
+            println!("aks({})={} passed", ele.0, result);
+        } else {
+            println!("aks({})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+
+    let result = (0..600i64).all(|n| aks(&Scalable::from(n)) == is_prime(&Scalable::from(n)));
+    if result {
+        println!("aks agrees with is_prime below 600 passed");
+    } else {
+        println!("aks agrees with is_prime below 600 error");
+    }
+}
+
+pub fn test_compare_primality_tests() {
+    let mut rng = SplitMix64::new(40);
+
+    for n in ["7919", "65537", "1022117"] {
+        let timings = compare_primality_tests(&s(n), &mut rng);
+        let result = timings.iter().all(|t| t.is_prime == timings[0].is_prime);
+        let summary: Vec<String> = timings
+            .iter()
+            .map(|t| format!("{} {:?}", t.test, t.elapsed))
+            .collect();
+        if result {
+            println!(
+                "compare_primality_tests({n})={} [{}] passed",
+                timings[0].is_prime,
+                summary.join(", ")
+            );
+        } else {
+            println!("compare_primality_tests({n})={timings:?} error: tests disagree");
+        }
+    }
+}
+
+pub fn test_test_primes() {
+    test_is_prime();
+    println!();
+    test_is_pseudo_prime();
+    println!();
+    test_is_euler_pseudo_prime();
+    println!();
+    test_solovay_strassen();
+    println!();
+    test_is_strong_pseudo_prime();
+    println!();
+    test_miller_rabin();
+    println!();
+    test_lucas_sequence();
+    println!();
+    test_is_strong_lucas_pseudo_prime();
+    println!();
+    test_baillie_psw();
+    println!();
+    test_lucas_lehmer();
+    println!();
+    test_proth();
+    println!();
+    test_pepin();
+    println!();
+    test_aks();
+    println!();
+    test_compare_primality_tests();
+    println!();
+}
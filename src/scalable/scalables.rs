@@ -1 +1,932 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Shl, Shr, Sub};
 
+type Limb = u64;
+type Wide = u128;
+const LIMB_BITS: usize = 64;
+
+/* Arbitrary size integer.
+   Stored as a sign and a magnitude made of 64 bits limbs, least
+   significant limb first. The magnitude never ends with a zero limb and
+   zero is never negative, so two equal integers are always represented
+   the same way.
+*/
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub struct Scalable {
+    negative: bool,
+    limbs: Vec<Limb>,
+}
+
+/********** Magnitude helpers **********/
+
+fn trim(limbs: &mut Vec<Limb>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_mag(a: &[Limb], b: &[Limb]) -> Ordering {
+    if a.len() != b.len() {
+        return a.len().cmp(&b.len());
+    }
+    for (x, y) in a.iter().rev().zip(b.iter().rev()) {
+        if x != y {
+            return x.cmp(y);
+        }
+    }
+    Ordering::Equal
+}
+
+fn add_mag(a: &[Limb], b: &[Limb]) -> Vec<Limb> {
+    let (a, b) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(a.len() + 1);
+    let mut carry = false;
+    for (i, x) in a.iter().enumerate() {
+        let (s, c1) = x.overflowing_add(*b.get(i).unwrap_or(&0));
+        let (s, c2) = s.overflowing_add(carry as Limb);
+        result.push(s);
+        carry = c1 || c2;
+    }
+    if carry {
+        result.push(1);
+    }
+    result
+}
+
+/* Requires a >= b. */
+fn sub_mag(a: &[Limb], b: &[Limb]) -> Vec<Limb> {
+    let mut result = Vec::with_capacity(a.len());
+    let mut borrow = false;
+    for (i, x) in a.iter().enumerate() {
+        let (d, b1) = x.overflowing_sub(*b.get(i).unwrap_or(&0));
+        let (d, b2) = d.overflowing_sub(borrow as Limb);
+        result.push(d);
+        borrow = b1 || b2;
+    }
+    trim(&mut result);
+    result
+}
+
+fn mul_mag(a: &[Limb], b: &[Limb]) -> Vec<Limb> {
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut result = vec![0; a.len() + b.len()];
+    for (i, x) in a.iter().enumerate() {
+        let mut carry: Wide = 0;
+        for (j, y) in b.iter().enumerate() {
+            let t = (*x as Wide) * (*y as Wide) + result[i + j] as Wide + carry;
+            result[i + j] = t as Limb;
+            carry = t >> LIMB_BITS;
+        }
+        result[i + b.len()] = carry as Limb;
+    }
+    trim(&mut result);
+    result
+}
+
+fn shl_mag(a: &[Limb], k: usize) -> Vec<Limb> {
+    if a.is_empty() {
+        return vec![];
+    }
+    let (limbs, bits) = (k / LIMB_BITS, k % LIMB_BITS);
+    let mut result = vec![0; limbs];
+    if bits == 0 {
+        result.extend_from_slice(a);
+    } else {
+        let mut carry = 0;
+        for x in a {
+            result.push((x << bits) | carry);
+            carry = x >> (LIMB_BITS - bits);
+        }
+        result.push(carry);
+    }
+    trim(&mut result);
+    result
+}
+
+fn shr_mag(a: &[Limb], k: usize) -> Vec<Limb> {
+    let (limbs, bits) = (k / LIMB_BITS, k % LIMB_BITS);
+    if limbs >= a.len() {
+        return vec![];
+    }
+    let a = &a[limbs..];
+    let mut result = Vec::with_capacity(a.len());
+    if bits == 0 {
+        result.extend_from_slice(a);
+    } else {
+        for i in 0..a.len() {
+            let high = a.get(i + 1).map_or(0, |x| x << (LIMB_BITS - bits));
+            result.push((a[i] >> bits) | high);
+        }
+    }
+    trim(&mut result);
+    result
+}
+
+fn divrem_small(a: &[Limb], d: Limb) -> (Vec<Limb>, Limb) {
+    let mut q = vec![0; a.len()];
+    let mut r: Wide = 0;
+    for i in (0..a.len()).rev() {
+        let cur = (r << LIMB_BITS) | a[i] as Wide;
+        q[i] = (cur / d as Wide) as Limb;
+        r = cur % d as Wide;
+    }
+    trim(&mut q);
+    (q, r as Limb)
+}
+
+/* Truncated division of magnitudes, Knuth's algorithm D.
+   Requires b to be non-zero.
+*/
+fn divrem_mag(a: &[Limb], b: &[Limb]) -> (Vec<Limb>, Vec<Limb>) {
+    if cmp_mag(a, b) == Ordering::Less {
+        return (vec![], a.to_vec());
+    }
+    if b.len() == 1 {
+        let (q, r) = divrem_small(a, b[0]);
+        let mut r = vec![r];
+        trim(&mut r);
+        return (q, r);
+    }
+    let n = b.len();
+    let m = a.len() - n;
+    let s = b[n - 1].leading_zeros() as usize;
+    let mut vn = shl_mag(b, s);
+    vn.resize(n, 0);
+    let mut un = shl_mag(a, s);
+    un.resize(a.len() + 1, 0);
+    let base: Wide = 1 << LIMB_BITS;
+    let mut q = vec![0; m + 1];
+
+    for j in (0..=m).rev() {
+        let num = ((un[j + n] as Wide) << LIMB_BITS) | un[j + n - 1] as Wide;
+        let mut qhat = num / vn[n - 1] as Wide;
+        let mut rhat = num % vn[n - 1] as Wide;
+        while qhat >= base
+            || qhat * vn[n - 2] as Wide > ((rhat << LIMB_BITS) | un[j + n - 2] as Wide)
+        {
+            qhat -= 1;
+            rhat += vn[n - 1] as Wide;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        let mut carry: Wide = 0;
+        let mut borrow = false;
+        for i in 0..n {
+            let p = qhat * vn[i] as Wide + carry;
+            carry = p >> LIMB_BITS;
+            let (t, b1) = un[i + j].overflowing_sub(p as Limb);
+            let (t, b2) = t.overflowing_sub(borrow as Limb);
+            un[i + j] = t;
+            borrow = b1 || b2;
+        }
+        let (t, b1) = un[j + n].overflowing_sub(carry as Limb);
+        let (t, b2) = t.overflowing_sub(borrow as Limb);
+        un[j + n] = t;
+
+        if b1 || b2 {
+            qhat -= 1;
+            let mut carry = false;
+            for i in 0..n {
+                let (t, c1) = un[i + j].overflowing_add(vn[i]);
+                let (t, c2) = t.overflowing_add(carry as Limb);
+                un[i + j] = t;
+                carry = c1 || c2;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as Limb);
+        }
+        q[j] = qhat as Limb;
+    }
+
+    trim(&mut q);
+    un.truncate(n);
+    trim(&mut un);
+    (q, shr_mag(&un, s))
+}
+
+/********** Scalable integers **********/
+
+impl Scalable {
+    fn from_parts(negative: bool, mut limbs: Vec<Limb>) -> Scalable {
+        trim(&mut limbs);
+        let negative = negative && !limbs.is_empty();
+        Scalable { negative, limbs }
+    }
+
+    pub fn zero() -> Scalable {
+        Scalable::default()
+    }
+
+    pub fn one() -> Scalable {
+        Scalable::from(1u64)
+    }
+
+    /* Power of two 2^k.
+       @param k exponent
+    */
+    pub fn pow2(k: usize) -> Scalable {
+        Scalable::one() << k
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn is_one(&self) -> bool {
+        !self.negative && self.limbs == [1]
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|x| x & 1 == 1)
+    }
+
+    pub fn is_even(&self) -> bool {
+        !self.is_odd()
+    }
+
+    pub fn abs(&self) -> Scalable {
+        Scalable::from_parts(false, self.limbs.clone())
+    }
+
+    /* Number of bits of the absolute value, 0 for 0. */
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            None => 0,
+            Some(top) => self.limbs.len() * LIMB_BITS - top.leading_zeros() as usize,
+        }
+    }
+
+    /* Bit of index i of the absolute value.
+       @param i index of the bit, 0 being least significant.
+    */
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / LIMB_BITS)
+            .is_some_and(|x| (x >> (i % LIMB_BITS)) & 1 == 1)
+    }
+
+    /* Set bit of index i of the absolute value to 1.
+       @param i index of the bit, 0 being least significant.
+    */
+    pub fn set_bit(&mut self, i: usize) {
+        if self.limbs.len() <= i / LIMB_BITS {
+            self.limbs.resize(i / LIMB_BITS + 1, 0);
+        }
+        self.limbs[i / LIMB_BITS] |= 1 << (i % LIMB_BITS);
+    }
+
+    /* Natural number made of bits start to start + len - 1 of the
+       absolute value.
+       @param start index of the lowest extracted bit.
+       @param len number of extracted bits.
+    */
+    pub fn bit_slice(&self, start: usize, len: usize) -> Scalable {
+        let first = start / LIMB_BITS;
+        if first >= self.limbs.len() || len == 0 {
+            return Scalable::zero();
+        }
+        let end = self.limbs.len().min((start + len).div_ceil(LIMB_BITS) + 1);
+        let mut limbs = shr_mag(&self.limbs[first..end], start % LIMB_BITS);
+        limbs.truncate(len.div_ceil(LIMB_BITS));
+        if !len.is_multiple_of(LIMB_BITS)
+            && let Some(top) = limbs.get_mut(len / LIMB_BITS)
+        {
+            *top &= (1 << (len % LIMB_BITS)) - 1;
+        }
+        Scalable::from_parts(false, limbs)
+    }
+
+    /* Number of trailing zero bits, 0 for 0. */
+    pub fn trailing_zeros(&self) -> usize {
+        match self.limbs.iter().position(|x| *x != 0) {
+            None => 0,
+            Some(i) => i * LIMB_BITS + self.limbs[i].trailing_zeros() as usize,
+        }
+    }
+
+    /* Conversion to a machine integer, None when it does not fit. */
+    pub fn to_i64(&self) -> Option<i64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 if self.negative && self.limbs[0] <= 1 << 63 => {
+                Some((self.limbs[0] as i64).wrapping_neg())
+            }
+            1 if !self.negative && self.limbs[0] < 1 << 63 => Some(self.limbs[0] as i64),
+            _ => None,
+        }
+    }
+
+    /* Conversion to an unsigned machine integer, None when it does not fit. */
+    pub fn to_u64(&self) -> Option<u64> {
+        match (self.negative, self.limbs.len()) {
+            (_, 0) => Some(0),
+            (false, 1) => Some(self.limbs[0]),
+            _ => None,
+        }
+    }
+
+    /* Euclidean modulo by a machine integer, faster than going through
+       `modulo` when the moduli is small.
+       @param m non-zero moduli.
+    */
+    pub fn rem_u64(&self, m: u64) -> u64 {
+        let (_, r) = divrem_small(&self.limbs, m);
+        if self.negative && r != 0 { m - r } else { r }
+    }
+
+    /* Integer from its big endian unsigned byte representation.
+       @param bytes big endian bytes, leading zeros are allowed.
+    */
+    pub fn from_bytes_be(bytes: &[u8]) -> Scalable {
+        let mut limbs = Vec::with_capacity(bytes.len() / 8 + 1);
+        for chunk in bytes.rchunks(8) {
+            let mut buf = [0; 8];
+            buf[8 - chunk.len()..].copy_from_slice(chunk);
+            limbs.push(Limb::from_be_bytes(buf));
+        }
+        Scalable::from_parts(false, limbs)
+    }
+
+    /* Big endian unsigned byte representation of the absolute value,
+       without leading zeros.
+    */
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        let mut result: Vec<u8> = self
+            .limbs
+            .iter()
+            .rev()
+            .flat_map(|x| x.to_be_bytes())
+            .collect();
+        let zeros = result.iter().take_while(|x| **x == 0).count();
+        result.drain(..zeros);
+        result
+    }
+
+    /* Big endian unsigned byte representation of the absolute value left
+       padded with zeros to len bytes. None when it does not fit.
+       @param len length of the output.
+    */
+    pub fn to_bytes_be_len(&self, len: usize) -> Option<Vec<u8>> {
+        let bytes = self.to_bytes_be();
+        if bytes.len() > len {
+            return None;
+        }
+        let mut result = vec![0; len - bytes.len()];
+        result.extend(bytes);
+        Some(result)
+    }
+
+    /* Parse an integer written in given radix, with an optional leading
+       minus sign. Underscores are ignored.
+       @param s string to parse.
+       @param radix base between 2 and 36.
+    */
+    pub fn from_str_radix(s: &str, radix: u32) -> Option<Scalable> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s),
+        };
+        let mut limbs: Vec<Limb> = vec![];
+        let mut seen = false;
+        for c in digits.chars().filter(|c| *c != '_') {
+            let d = c.to_digit(radix)? as Wide;
+            seen = true;
+            let mut carry = d;
+            for x in limbs.iter_mut() {
+                let t = (*x as Wide) * radix as Wide + carry;
+                *x = t as Limb;
+                carry = t >> LIMB_BITS;
+            }
+            if carry != 0 {
+                limbs.push(carry as Limb);
+            }
+        }
+        if !seen {
+            return None;
+        }
+        Some(Scalable::from_parts(negative, limbs))
+    }
+
+    /* Parse an hexadecimal integer, see `from_str_radix`. */
+    pub fn from_hex(s: &str) -> Option<Scalable> {
+        Scalable::from_str_radix(s, 16)
+    }
+}
+
+impl From<u64> for Scalable {
+    fn from(x: u64) -> Scalable {
+        Scalable::from_parts(false, vec![x])
+    }
+}
+
+impl From<i64> for Scalable {
+    fn from(x: i64) -> Scalable {
+        Scalable::from_parts(x < 0, vec![x.unsigned_abs()])
+    }
+}
+
+impl Ord for Scalable {
+    fn cmp(&self, other: &Scalable) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => cmp_mag(&self.limbs, &other.limbs),
+            (true, true) => cmp_mag(&other.limbs, &self.limbs),
+        }
+    }
+}
+
+impl PartialOrd for Scalable {
+    fn partial_cmp(&self, other: &Scalable) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Scalable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        const CHUNK: Limb = 10_000_000_000_000_000_000;
+        let mut chunks = vec![];
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (q, r) = divrem_small(&rest, CHUNK);
+            chunks.push(r);
+            rest = q;
+        }
+        let mut s = String::new();
+        if self.negative {
+            s.push('-');
+        }
+        s.push_str(&chunks.pop().unwrap().to_string());
+        for c in chunks.iter().rev() {
+            s.push_str(&format!("{:019}", c));
+        }
+        f.pad(&s)
+    }
+}
+
+impl fmt::LowerHex for Scalable {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+        let mut s = String::new();
+        if self.negative {
+            s.push('-');
+        }
+        s.push_str(&format!("{:x}", self.limbs.last().unwrap()));
+        for x in self.limbs.iter().rev().skip(1) {
+            s.push_str(&format!("{:016x}", x));
+        }
+        f.pad(&s)
+    }
+}
+
+/********** Operators **********/
+
+fn add_signed(a: &Scalable, b: &Scalable, b_negative: bool) -> Scalable {
+    if a.negative == b_negative {
+        return Scalable::from_parts(a.negative, add_mag(&a.limbs, &b.limbs));
+    }
+    match cmp_mag(&a.limbs, &b.limbs) {
+        Ordering::Less => Scalable::from_parts(b_negative, sub_mag(&b.limbs, &a.limbs)),
+        _ => Scalable::from_parts(a.negative, sub_mag(&a.limbs, &b.limbs)),
+    }
+}
+
+impl Add<&Scalable> for &Scalable {
+    type Output = Scalable;
+    fn add(self, other: &Scalable) -> Scalable {
+        add_signed(self, other, other.negative)
+    }
+}
+
+impl Sub<&Scalable> for &Scalable {
+    type Output = Scalable;
+    fn sub(self, other: &Scalable) -> Scalable {
+        add_signed(self, other, !other.negative)
+    }
+}
+
+impl Mul<&Scalable> for &Scalable {
+    type Output = Scalable;
+    fn mul(self, other: &Scalable) -> Scalable {
+        Scalable::from_parts(
+            self.negative != other.negative,
+            mul_mag(&self.limbs, &other.limbs),
+        )
+    }
+}
+
+/* Euclidean quotient, see `quot`. */
+impl Div<&Scalable> for &Scalable {
+    type Output = Scalable;
+    fn div(self, other: &Scalable) -> Scalable {
+        div(self, other).0
+    }
+}
+
+/* Euclidean modulo, always non-negative, see `modulo`. */
+impl Rem<&Scalable> for &Scalable {
+    type Output = Scalable;
+    fn rem(self, other: &Scalable) -> Scalable {
+        div(self, other).1
+    }
+}
+
+/* Shifts act on the absolute value and keep the sign. */
+impl Shl<usize> for &Scalable {
+    type Output = Scalable;
+    fn shl(self, k: usize) -> Scalable {
+        Scalable::from_parts(self.negative, shl_mag(&self.limbs, k))
+    }
+}
+
+impl Shr<usize> for &Scalable {
+    type Output = Scalable;
+    fn shr(self, k: usize) -> Scalable {
+        Scalable::from_parts(self.negative, shr_mag(&self.limbs, k))
+    }
+}
+
+impl Neg for &Scalable {
+    type Output = Scalable;
+    fn neg(self) -> Scalable {
+        Scalable::from_parts(!self.negative, self.limbs.clone())
+    }
+}
+
+impl Neg for Scalable {
+    type Output = Scalable;
+    fn neg(self) -> Scalable {
+        Scalable::from_parts(!self.negative, self.limbs)
+    }
+}
+
+impl Shl<usize> for Scalable {
+    type Output = Scalable;
+    fn shl(self, k: usize) -> Scalable {
+        &self << k
+    }
+}
+
+impl Shr<usize> for Scalable {
+    type Output = Scalable;
+    fn shr(self, k: usize) -> Scalable {
+        &self >> k
+    }
+}
+
+/* Owned and machine integer variants of the binary operators, all
+   forwarding to the reference implementation above.
+*/
+macro_rules! forward_binop {
+    ($trait:ident, $method:ident) => {
+        impl $trait<Scalable> for Scalable {
+            type Output = Scalable;
+            fn $method(self, other: Scalable) -> Scalable {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<&Scalable> for Scalable {
+            type Output = Scalable;
+            fn $method(self, other: &Scalable) -> Scalable {
+                (&self).$method(other)
+            }
+        }
+
+        impl $trait<Scalable> for &Scalable {
+            type Output = Scalable;
+            fn $method(self, other: Scalable) -> Scalable {
+                self.$method(&other)
+            }
+        }
+
+        impl $trait<i64> for &Scalable {
+            type Output = Scalable;
+            fn $method(self, other: i64) -> Scalable {
+                self.$method(&Scalable::from(other))
+            }
+        }
+
+        impl $trait<i64> for Scalable {
+            type Output = Scalable;
+            fn $method(self, other: i64) -> Scalable {
+                (&self).$method(&Scalable::from(other))
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+forward_binop!(Div, div);
+forward_binop!(Rem, rem);
+
+impl PartialEq<i64> for Scalable {
+    fn eq(&self, other: &i64) -> bool {
+        self.to_i64() == Some(*other)
+    }
+}
+
+/********** Euclidean division **********/
+
+/* Sign function
+    @param x integer
+*/
+pub fn sign(x: &Scalable) -> i64 {
+    if x.is_negative() { -1 } else { 1 }
+}
+
+/* Quotient of an integer by a natural number.
+    This is the quotient in euclidiant division sense.
+    @param a dividend
+    @param b natural number you divide by.
+*/
+pub fn quot(a: &Scalable, b: &Scalable) -> Scalable {
+    div(a, b).0
+}
+
+/* Modulo of two integers.
+   Following euclidean division NOT RUST DEFAULT. Positive integer
+   between 0 (included) and modulo (excluded) resulting from euclidian
+   division of entry by modulo.
+
+   @param a input integer
+   @param b moduli integer.
+*/
+pub fn modulo(a: &Scalable, b: &Scalable) -> Scalable {
+    div(a, b).1
+}
+
+/* Division of an integer by a non-zero integer. NOT RUST DEFAULT.
+   Division of an integer by a non-zero integer b is the unique couple
+   of integers (q, r) such that a = b*q + r and r is in [0, abs b[.
+   @param a dividend
+   @param b integer you divide by.
+*/
+pub fn div(a: &Scalable, b: &Scalable) -> (Scalable, Scalable) {
+    if b.is_zero() {
+        panic!("Division by zero");
+    }
+    let (q, r) = divrem_mag(&a.limbs, &b.limbs);
+    let q = Scalable::from_parts(a.negative != b.negative, q);
+    let r = Scalable::from_parts(a.negative, r);
+    if r.is_negative() {
+        let one = Scalable::from(sign(b));
+        (&q - &one, &r + &b.abs())
+    } else {
+        (q, r)
+    }
+}
+
+// ========================= TESTING =========================
+
+fn s(x: &str) -> Scalable {
+    Scalable::from_str_radix(x, 10).unwrap()
+}
+
+pub fn test_sign() {
+    let cases = vec![
+        ("1", 1),
+        ("-1", -1),
+        ("0", 1),
+        ("-123456789012345678901234567890", -1),
+    ];
+
+    for ele in cases {
+        let result = sign(&s(ele.0));
+        if result == ele.1 {
+            println!("sign({})={} passed", ele.0, result);
+        } else {
+            println!("sign({})={} error: expected {}", ele.0, result, ele.1);
+        }
+    }
+}
+
+pub fn test_quot() {
+    let cases = vec![
+        (("10", "3"), "3"),
+        (("-10", "3"), "-4"),
+        (("10", "2"), "5"),
+        (("-10", "2"), "-5"),
+        (
+            (
+                "340282366920938463463374607431768211457",
+                "18446744073709551617",
+            ),
+            "18446744073709551615",
+        ),
+        (
+            (
+                "-340282366920938463463374607431768211457",
+                "18446744073709551617",
+            ),
+            "-18446744073709551616",
+        ),
+    ];
+
+    for ele in cases {
+        let result = quot(&s(ele.0.0), &s(ele.0.1));
+        if result == s(ele.1) {
+            println!("quot({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "quot({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_modulo() {
+    let cases = vec![
+        (("10", "3"), "1"),
+        (("-10", "3"), "2"),
+        (("10", "2"), "0"),
+        (("-10", "2"), "0"),
+        (
+            (
+                "340282366920938463463374607431768211457",
+                "18446744073709551617",
+            ),
+            "2",
+        ),
+        (
+            (
+                "-340282366920938463463374607431768211457",
+                "18446744073709551617",
+            ),
+            "18446744073709551615",
+        ),
+    ];
+
+    for ele in cases {
+        let result = modulo(&s(ele.0.0), &s(ele.0.1));
+        if result == s(ele.1) {
+            println!("modulo({},{})={} passed", ele.0.0, ele.0.1, result);
+        } else {
+            println!(
+                "modulo({},{})={} error: expected {}",
+                ele.0.0, ele.0.1, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_div() {
+    let cases = vec![
+        (("10", "3"), ("3", "1")),
+        (("-10", "3"), ("-4", "2")),
+        (("10", "2"), ("5", "0")),
+        (("-10", "2"), ("-5", "0")),
+        (("10", "-3"), ("-3", "1")),
+        (("-10", "-3"), ("4", "2")),
+        (
+            (
+                "1298074214633706835075030044377087",
+                "1267650600228229401496703205653",
+            ),
+            ("1023", "1267650600228157343902664994068"),
+        ),
+    ];
+
+    for ele in cases {
+        let result = div(&s(ele.0.0), &s(ele.0.1));
+        if result == (s(ele.1.0), s(ele.1.1)) {
+            println!(
+                "div({},{})=({},{}) passed",
+                ele.0.0, ele.0.1, result.0, result.1
+            );
+        } else {
+            println!(
+                "div({},{})=({},{}) error: expected {:?}",
+                ele.0.0, ele.0.1, result.0, result.1, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_operators() {
+    let cases = vec![
+        (
+            ("18446744073709551615", "1"),
+            (
+                "18446744073709551616",
+                "18446744073709551614",
+                "18446744073709551615",
+            ),
+        ),
+        (
+            ("-5", "18446744073709551616"),
+            (
+                "18446744073709551611",
+                "-18446744073709551621",
+                "-92233720368547758080",
+            ),
+        ),
+        (
+            (
+                "123456789012345678901234567890",
+                "-987654321098765432109876543210",
+            ),
+            (
+                "-864197532086419753208641975320",
+                "1111111110111111111011111111100",
+                "-121932631137021795226185032733622923332237463801111263526900",
+            ),
+        ),
+    ];
+
+    for ele in cases {
+        let (a, b) = (s(ele.0.0), s(ele.0.1));
+        let result = (&a + &b, &a - &b, &a * &b);
+        if result == (s(ele.1.0), s(ele.1.1), s(ele.1.2)) {
+            println!(
+                "ops({},{})=({},{},{}) passed",
+                a, b, result.0, result.1, result.2
+            );
+        } else {
+            println!(
+                "ops({},{})=({},{},{}) error: expected {:?}",
+                a, b, result.0, result.1, result.2, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_bytes() {
+    let cases = vec![
+        (vec![0x01], "1"),
+        (vec![0x00, 0x01, 0x00], "256"),
+        (
+            vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01],
+            "20988295479420645121",
+        ),
+    ];
+
+    for ele in cases {
+        let result = Scalable::from_bytes_be(&ele.0);
+        let back = result.to_bytes_be_len(ele.0.len());
+        if result == s(ele.1) && back.as_ref() == Some(&ele.0) {
+            println!("from_bytes_be({:?})={} passed", ele.0, result);
+        } else {
+            println!(
+                "from_bytes_be({:?})={} error: expected {}",
+                ele.0, result, ele.1
+            );
+        }
+    }
+}
+
+pub fn test_bit_slice() {
+    let x = s("340282366920938463463374607431768211455") * 3;
+    let cases = vec![
+        ((s("255"), 0, 4), "15"),
+        ((s("255"), 4, 8), "15"),
+        ((s("-1024"), 10, 1), "1"),
+        ((s("5"), 64, 10), "0"),
+        ((x.clone(), 60, 8), "255"),
+        ((x.clone(), 127, 3), "5"),
+        ((x, 0, 200), "1020847100762815390390123822295304634365"),
+    ];
+
+    for ele in cases {
+        let ((x, start, len), exp) = ele;
+        let result = x.bit_slice(start, len);
+        if result == s(exp) {
+            println!("bit_slice({x},{start},{len})={result} passed");
+        } else {
+            println!("bit_slice({x},{start},{len})={result} error: expected {exp}");
+        }
+    }
+}
+
+pub fn test_scalables() {
+    test_sign();
+    println!();
+    test_quot();
+    println!();
+    test_modulo();
+    println!();
+    test_div();
+    println!();
+    test_operators();
+    println!();
+    test_bytes();
+    println!();
+    test_bit_slice();
+    println!();
+}